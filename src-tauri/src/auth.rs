@@ -0,0 +1,598 @@
+//! Built-in authentication helpers: Basic, Bearer, HTTP Digest and OAuth2 client credentials.
+//!
+//! Credentials are applied to the fully built request in `make_request`, so they always
+//! match the final method and URL (cache buster included).
+
+use crate::LoadTestError;
+use base64::Engine;
+use md5::Md5;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, WWW_AUTHENTICATE};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Refresh OAuth2 tokens this long before they expire (capped at 1/5 of the token lifetime)
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(30);
+/// Token lifetime assumed when the token endpoint does not return `expires_in`
+const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(3600);
+/// Longest token lifetime honoured; larger `expires_in` values are capped
+const MAX_TOKEN_LIFETIME: Duration = Duration::from_secs(86_400);
+/// Timeout for token endpoint requests
+const TOKEN_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Authentication applied to every request
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum AuthConfig {
+    /// HTTP Basic authentication
+    Basic { username: String, password: String },
+    /// Static bearer token
+    Bearer { token: String },
+    /// HTTP Digest authentication; the challenge is taken from the server's 401 responses
+    Digest { username: String, password: String },
+    /// OAuth2 client credentials grant, with the token shared by all workers and
+    /// refreshed before it expires
+    OAuth2ClientCredentials {
+        token_url: String,
+        client_id: String,
+        client_secret: String,
+        #[serde(default)]
+        scope: Option<String>,
+        /// Send the client credentials in the form body instead of a Basic auth header
+        #[serde(default)]
+        credentials_in_body: bool,
+    },
+}
+
+/// Authentication activity during a test, reported separately from target failures
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AuthStats {
+    /// Token endpoint requests made (including the initial fetch)
+    pub token_fetches: u32,
+    pub token_fetch_failures: u32,
+    pub last_token_error: Option<String>,
+    /// Digest challenges accepted from the server (initial and stale nonces)
+    pub digest_challenges: u32,
+}
+
+/// Applies the configured authentication to requests
+pub(crate) struct Authenticator {
+    scheme: AuthScheme,
+}
+
+enum AuthScheme {
+    /// Precomputed Authorization header (Basic, Bearer)
+    Static(HeaderValue),
+    Digest(DigestAuth),
+    OAuth2(Arc<OAuth2Source>),
+}
+
+impl Authenticator {
+    /// Creates the authenticator. OAuth2 tokens are fetched up front so a misconfigured
+    /// token endpoint fails the test before any load is sent.
+    pub(crate) async fn new(config: AuthConfig) -> Result<Self, LoadTestError> {
+        let scheme = match config {
+            AuthConfig::Basic { username, password } => {
+                let credentials = base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", username, password));
+                AuthScheme::Static(header_value(&format!("Basic {}", credentials))?)
+            }
+            AuthConfig::Bearer { token } => AuthScheme::Static(header_value(&format!("Bearer {}", token))?),
+            AuthConfig::Digest { username, password } => AuthScheme::Digest(DigestAuth {
+                username,
+                password,
+                challenge: RwLock::new(None),
+                challenges: AtomicU32::new(0),
+            }),
+            AuthConfig::OAuth2ClientCredentials { token_url, client_id, client_secret, scope, credentials_in_body } => {
+                let client = reqwest::Client::builder().timeout(TOKEN_FETCH_TIMEOUT).build()?;
+                let source = Arc::new(OAuth2Source {
+                    client,
+                    token_url,
+                    client_id,
+                    client_secret,
+                    scope,
+                    credentials_in_body,
+                    token: RwLock::new(None),
+                    refresh_lock: Arc::new(tokio::sync::Mutex::new(())),
+                    fetches: AtomicU32::new(0),
+                    failures: AtomicU32::new(0),
+                    last_error: Mutex::new(None),
+                });
+                source.refresh().await.map_err(LoadTestError::InvalidConfig)?;
+                AuthScheme::OAuth2(source)
+            }
+        };
+        Ok(Self { scheme })
+    }
+
+    /// Adds the Authorization header to a fully built request.
+    /// Fails only when no OAuth2 token could be obtained.
+    pub(crate) async fn authorize(&self, request: &mut reqwest::Request) -> Result<(), String> {
+        let value = match &self.scheme {
+            AuthScheme::Static(value) => Some(value.clone()),
+            AuthScheme::Digest(digest) => digest.authorization(request),
+            AuthScheme::OAuth2(source) => Some(source.authorization().await?),
+        };
+        if let Some(value) = value {
+            request.headers_mut().insert(AUTHORIZATION, value);
+        }
+        Ok(())
+    }
+
+    /// Sends a request, answering a Digest challenge (first request or stale nonce) with
+    /// a single retry. Other schemes send the request as is.
+    pub(crate) async fn execute(
+        &self,
+        client: &reqwest::Client,
+        request: reqwest::Request,
+    ) -> reqwest::Result<reqwest::Response> {
+        let AuthScheme::Digest(digest) = &self.scheme else {
+            return client.execute(request).await;
+        };
+
+        // Keep a copy to answer the challenge with (streaming bodies such as multipart can't be cloned)
+        let retry = request.try_clone();
+        let response = client.execute(request).await?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+        let Some(mut retry) = retry else {
+            return Ok(response);
+        };
+        if !digest.update_challenge(response.headers()) {
+            return Ok(response);
+        }
+
+        // Drain the 401 so its connection can be reused for the retry
+        let _ = response.bytes().await;
+        if let Some(value) = digest.authorization(&retry) {
+            retry.headers_mut().insert(AUTHORIZATION, value);
+        }
+        client.execute(retry).await
+    }
+
+    pub(crate) fn stats(&self) -> AuthStats {
+        match &self.scheme {
+            AuthScheme::Static(_) => AuthStats::default(),
+            AuthScheme::Digest(digest) => AuthStats {
+                digest_challenges: digest.challenges.load(Ordering::Relaxed),
+                ..AuthStats::default()
+            },
+            AuthScheme::OAuth2(source) => AuthStats {
+                token_fetches: source.fetches.load(Ordering::Relaxed),
+                token_fetch_failures: source.failures.load(Ordering::Relaxed),
+                last_token_error: source.last_error.lock().ok().and_then(|error| error.clone()),
+                ..AuthStats::default()
+            },
+        }
+    }
+}
+
+fn header_value(value: &str) -> Result<HeaderValue, LoadTestError> {
+    HeaderValue::from_str(value)
+        .map_err(|_| LoadTestError::InvalidConfig("Credentials contain characters not allowed in a header".into()))
+}
+
+/// Digest hash algorithm from the server's challenge
+#[derive(Debug, Clone, Copy)]
+enum DigestAlgorithm {
+    Md5,
+    Md5Sess,
+    Sha256,
+    Sha256Sess,
+}
+
+impl DigestAlgorithm {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "MD5" => Some(Self::Md5),
+            "MD5-SESS" => Some(Self::Md5Sess),
+            "SHA-256" => Some(Self::Sha256),
+            "SHA-256-SESS" => Some(Self::Sha256Sess),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Md5 => "MD5",
+            Self::Md5Sess => "MD5-sess",
+            Self::Sha256 => "SHA-256",
+            Self::Sha256Sess => "SHA-256-sess",
+        }
+    }
+
+    fn is_session(self) -> bool {
+        matches!(self, Self::Md5Sess | Self::Sha256Sess)
+    }
+
+    fn hash(self, data: &str) -> String {
+        match self {
+            Self::Md5 | Self::Md5Sess => format!("{:x}", Md5::digest(data.as_bytes())),
+            Self::Sha256 | Self::Sha256Sess => format!("{:x}", Sha256::digest(data.as_bytes())),
+        }
+    }
+}
+
+/// Server nonce and parameters, shared by all workers until the server issues a new one
+struct DigestChallenge {
+    realm: String,
+    nonce: String,
+    opaque: Option<String>,
+    algorithm: DigestAlgorithm,
+    /// Whether qop=auth is used (qop=auth-int is not supported)
+    qop_auth: bool,
+    /// Requests sent with this nonce (the `nc` value)
+    nonce_count: AtomicU32,
+}
+
+struct DigestAuth {
+    username: String,
+    password: String,
+    challenge: RwLock<Option<Arc<DigestChallenge>>>,
+    challenges: AtomicU32,
+}
+
+impl DigestAuth {
+    /// Computes the Authorization header for a request, or None before the first challenge
+    fn authorization(&self, request: &reqwest::Request) -> Option<HeaderValue> {
+        self.authorization_with_cnonce(request, &format!("{:016x}", rand::random::<u64>()))
+    }
+
+    fn authorization_with_cnonce(&self, request: &reqwest::Request, cnonce: &str) -> Option<HeaderValue> {
+        let challenge = self.challenge.read().ok()?.clone()?;
+        let url = request.url();
+        let uri = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+
+        let algorithm = challenge.algorithm;
+        let nonce_count = format!("{:08x}", challenge.nonce_count.fetch_add(1, Ordering::Relaxed) + 1);
+
+        let mut ha1 = algorithm.hash(&format!("{}:{}:{}", self.username, challenge.realm, self.password));
+        if algorithm.is_session() {
+            ha1 = algorithm.hash(&format!("{}:{}:{}", ha1, challenge.nonce, cnonce));
+        }
+        let ha2 = algorithm.hash(&format!("{}:{}", request.method(), uri));
+        let response = if challenge.qop_auth {
+            algorithm.hash(&format!("{}:{}:{}:{}:auth:{}", ha1, challenge.nonce, nonce_count, cnonce, ha2))
+        } else {
+            algorithm.hash(&format!("{}:{}:{}", ha1, challenge.nonce, ha2))
+        };
+
+        let mut header = format!(
+            "Digest username=\"{}\", realm=\"{}\", nonce=\"{}\", uri=\"{}\", algorithm={}, response=\"{}\"",
+            self.username, challenge.realm, challenge.nonce, uri, algorithm.name(), response
+        );
+        if challenge.qop_auth {
+            header.push_str(&format!(", qop=auth, nc={}, cnonce=\"{}\"", nonce_count, cnonce));
+        }
+        if let Some(opaque) = &challenge.opaque {
+            header.push_str(&format!(", opaque=\"{}\"", opaque));
+        }
+        HeaderValue::from_str(&header).ok()
+    }
+
+    /// Stores the first supported Digest challenge from a 401 response.
+    /// Returns false if the response has no usable challenge.
+    fn update_challenge(&self, headers: &HeaderMap) -> bool {
+        let challenge = headers
+            .get_all(WWW_AUTHENTICATE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| {
+                let (scheme, params) = value.trim().split_once(' ')?;
+                scheme.eq_ignore_ascii_case("Digest").then(|| parse_auth_params(params))
+            })
+            .find_map(|params| {
+                let algorithm = match params.get("algorithm") {
+                    Some(name) => DigestAlgorithm::parse(name)?,
+                    None => DigestAlgorithm::Md5,
+                };
+                let qop_auth = match params.get("qop") {
+                    Some(qop) => qop.split(',').any(|option| option.trim().eq_ignore_ascii_case("auth")),
+                    None => false,
+                };
+                // Servers that only offer qop=auth-int can't be answered
+                if params.contains_key("qop") && !qop_auth {
+                    return None;
+                }
+                Some(DigestChallenge {
+                    realm: params.get("realm").cloned().unwrap_or_default(),
+                    nonce: params.get("nonce")?.clone(),
+                    opaque: params.get("opaque").cloned(),
+                    algorithm,
+                    qop_auth,
+                    nonce_count: AtomicU32::new(0),
+                })
+            });
+
+        let Some(challenge) = challenge else {
+            return false;
+        };
+        let Ok(mut current) = self.challenge.write() else {
+            return false;
+        };
+        *current = Some(Arc::new(challenge));
+        self.challenges.fetch_add(1, Ordering::Relaxed);
+        true
+    }
+}
+
+/// Parses comma-separated `key=value` / `key="quoted, value"` auth parameters.
+/// Keys are lowercased.
+fn parse_auth_params(params: &str) -> HashMap<String, String> {
+    let mut result = HashMap::new();
+    let mut chars = params.chars().peekable();
+
+    loop {
+        // Key: up to '='
+        let key: String = chars
+            .by_ref()
+            .skip_while(|c| *c == ',' || c.is_whitespace())
+            .take_while(|c| *c != '=')
+            .collect();
+        if key.is_empty() {
+            break;
+        }
+
+        // Value: quoted (with backslash escapes) or up to the next comma
+        let mut value = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => value.extend(chars.next()),
+                    '"' => break,
+                    c => value.push(c),
+                }
+            }
+        } else {
+            value = chars.by_ref().take_while(|c| *c != ',').collect();
+        }
+        result.insert(key.trim().to_ascii_lowercase(), value.trim().to_string());
+    }
+    result
+}
+
+/// Access token with its refresh and expiry deadlines
+#[derive(Clone)]
+struct CachedToken {
+    header: HeaderValue,
+    refresh_at: Instant,
+    expires_at: Instant,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<f64>,
+}
+
+/// OAuth2 client credentials token source shared by all workers
+struct OAuth2Source {
+    client: reqwest::Client,
+    token_url: String,
+    client_id: String,
+    client_secret: String,
+    scope: Option<String>,
+    credentials_in_body: bool,
+    token: RwLock<Option<CachedToken>>,
+    /// Ensures a single token fetch at a time
+    refresh_lock: Arc<tokio::sync::Mutex<()>>,
+    fetches: AtomicU32,
+    failures: AtomicU32,
+    last_error: Mutex<Option<String>>,
+}
+
+impl OAuth2Source {
+    fn current(&self) -> Option<CachedToken> {
+        self.token.read().ok()?.clone()
+    }
+
+    /// Returns the Authorization header, refreshing the token in the background when it is
+    /// about to expire and waiting for a new one only when it already has
+    async fn authorization(self: &Arc<Self>) -> Result<HeaderValue, String> {
+        if let Some(token) = self.current() {
+            let now = Instant::now();
+            if now < token.refresh_at {
+                return Ok(token.header);
+            }
+            if now < token.expires_at {
+                // Still valid - one worker refreshes while the rest keep using it
+                if let Ok(guard) = Arc::clone(&self.refresh_lock).try_lock_owned() {
+                    let source = Arc::clone(self);
+                    tokio::spawn(async move {
+                        let _guard = guard;
+                        let _ = source.refresh().await;
+                    });
+                }
+                return Ok(token.header);
+            }
+        }
+
+        let _guard = self.refresh_lock.lock().await;
+        // Another worker may have refreshed while we waited
+        if let Some(token) = self.current().filter(|token| Instant::now() < token.expires_at) {
+            return Ok(token.header);
+        }
+        self.refresh().await.map(|token| token.header)
+    }
+
+    /// Fetches a new token and stores it, recording failures
+    async fn refresh(&self) -> Result<CachedToken, String> {
+        self.fetches.fetch_add(1, Ordering::Relaxed);
+        match self.fetch().await {
+            Ok(token) => {
+                if let Ok(mut current) = self.token.write() {
+                    *current = Some(token.clone());
+                }
+                Ok(token)
+            }
+            Err(e) => {
+                let message = format!("OAuth2 token fetch failed: {}", e);
+                self.failures.fetch_add(1, Ordering::Relaxed);
+                if let Ok(mut last_error) = self.last_error.lock() {
+                    *last_error = Some(message.clone());
+                }
+                Err(message)
+            }
+        }
+    }
+
+    async fn fetch(&self) -> Result<CachedToken, String> {
+        let mut form = vec![("grant_type", "client_credentials")];
+        if let Some(scope) = self.scope.as_deref().filter(|scope| !scope.is_empty()) {
+            form.push(("scope", scope));
+        }
+
+        let mut request = self.client.post(&self.token_url);
+        if self.credentials_in_body {
+            form.push(("client_id", &self.client_id));
+            form.push(("client_secret", &self.client_secret));
+        } else {
+            request = request.basic_auth(&self.client_id, Some(&self.client_secret));
+        }
+
+        let fetched_at = Instant::now();
+        let response = request.form(&form).send().await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {} from token endpoint", response.status().as_u16()));
+        }
+        let token: TokenResponse = response.json().await.map_err(|e| format!("Invalid token response: {}", e))?;
+
+        let lifetime = token_lifetime(token.expires_in);
+        let margin = TOKEN_REFRESH_MARGIN.min(lifetime / 5);
+        let header = HeaderValue::from_str(&format!("Bearer {}", token.access_token))
+            .map_err(|_| "Token contains characters not allowed in a header".to_string())?;
+
+        let expires_at = fetched_at
+            .checked_add(lifetime)
+            .unwrap_or_else(|| fetched_at + DEFAULT_TOKEN_LIFETIME);
+        Ok(CachedToken {
+            header,
+            refresh_at: expires_at.checked_sub(margin).unwrap_or(fetched_at),
+            expires_at,
+        })
+    }
+}
+
+/// Token lifetime from `expires_in` seconds, capped at `MAX_TOKEN_LIFETIME`.
+/// Missing, non-positive or NaN values fall back to `DEFAULT_TOKEN_LIFETIME`.
+fn token_lifetime(expires_in: Option<f64>) -> Duration {
+    expires_in
+        .filter(|secs| *secs > 0.0)
+        .and_then(|secs| Duration::try_from_secs_f64(secs.min(MAX_TOKEN_LIFETIME.as_secs_f64())).ok())
+        .unwrap_or(DEFAULT_TOKEN_LIFETIME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::{Method, Url};
+
+    /// RFC 7616 section 3.9.1 example challenge, with the algorithm filled in per test
+    fn rfc7616_digest(algorithm: &str) -> DigestAuth {
+        let auth = DigestAuth {
+            username: "Mufasa".into(),
+            password: "Circle of Life".into(),
+            challenge: RwLock::new(None),
+            challenges: AtomicU32::new(0),
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            WWW_AUTHENTICATE,
+            HeaderValue::from_str(&format!(
+                "Digest realm=\"http-auth@example.org\", qop=\"auth, auth-int\", algorithm={}, \
+                 nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", \
+                 opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\"",
+                algorithm
+            ))
+            .unwrap(),
+        );
+        assert!(auth.update_challenge(&headers));
+        auth
+    }
+
+    fn rfc7616_authorization(auth: &DigestAuth) -> HashMap<String, String> {
+        let request = reqwest::Request::new(Method::GET, Url::parse("http://www.example.org/dir/index.html").unwrap());
+        let header = auth
+            .authorization_with_cnonce(&request, "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ")
+            .unwrap();
+        let header = header.to_str().unwrap();
+        let params = header.strip_prefix("Digest ").expect("Digest scheme");
+        parse_auth_params(params)
+    }
+
+    #[test]
+    fn digest_rfc7616_md5() {
+        let params = rfc7616_authorization(&rfc7616_digest("MD5"));
+        assert_eq!(params["response"], "8ca523f5e9506fed4657c9700eebdbec");
+        assert_eq!(params["uri"], "/dir/index.html");
+        assert_eq!(params["qop"], "auth");
+        assert_eq!(params["nc"], "00000001");
+        assert_eq!(params["opaque"], "FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS");
+    }
+
+    #[test]
+    fn digest_rfc7616_sha256() {
+        let auth = rfc7616_digest("SHA-256");
+        let params = rfc7616_authorization(&auth);
+        assert_eq!(params["algorithm"], "SHA-256");
+        assert_eq!(params["response"], "753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1");
+        // The nonce count advances per request
+        assert_eq!(rfc7616_authorization(&auth)["nc"], "00000002");
+    }
+
+    #[test]
+    fn digest_rejects_auth_int_only_challenges() {
+        let auth = DigestAuth {
+            username: "user".into(),
+            password: "pass".into(),
+            challenge: RwLock::new(None),
+            challenges: AtomicU32::new(0),
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(WWW_AUTHENTICATE, HeaderValue::from_static("Digest realm=\"r\", nonce=\"n\", qop=\"auth-int\""));
+        assert!(!auth.update_challenge(&headers));
+        let request = reqwest::Request::new(Method::GET, Url::parse("http://example.org/").unwrap());
+        assert!(auth.authorization(&request).is_none());
+    }
+
+    #[test]
+    fn parse_auth_params_handles_quotes_and_escapes() {
+        let params = parse_auth_params(
+            r#"Realm="a, b", qop="auth, auth-int" ,nonce=abc123, opaque="say \"hi\" \\ bye", stale=FALSE"#,
+        );
+        assert_eq!(params.len(), 5);
+        assert_eq!(params["realm"], "a, b");
+        assert_eq!(params["qop"], "auth, auth-int");
+        assert_eq!(params["nonce"], "abc123");
+        assert_eq!(params["opaque"], r#"say "hi" \ bye"#);
+        assert_eq!(params["stale"], "FALSE");
+    }
+
+    #[test]
+    fn parse_auth_params_handles_empty_input() {
+        assert!(parse_auth_params("").is_empty());
+        assert!(parse_auth_params(" , ").is_empty());
+        assert_eq!(parse_auth_params(r#"realm="""#)["realm"], "");
+    }
+
+    #[test]
+    fn token_lifetime_is_bounded() {
+        assert_eq!(token_lifetime(None), DEFAULT_TOKEN_LIFETIME);
+        assert_eq!(token_lifetime(Some(0.0)), DEFAULT_TOKEN_LIFETIME);
+        assert_eq!(token_lifetime(Some(-5.0)), DEFAULT_TOKEN_LIFETIME);
+        assert_eq!(token_lifetime(Some(f64::NAN)), DEFAULT_TOKEN_LIFETIME);
+        assert_eq!(token_lifetime(Some(f64::INFINITY)), MAX_TOKEN_LIFETIME);
+        assert_eq!(token_lifetime(Some(1e30)), MAX_TOKEN_LIFETIME);
+        assert_eq!(token_lifetime(Some(299.5)), Duration::from_millis(299_500));
+    }
+}
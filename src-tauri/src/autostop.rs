@@ -0,0 +1,175 @@
+//! Safety stops evaluated on the result stream while a test runs.
+//!
+//! The collector in `run_load_test_inner` feeds every result to an `AutoStopMonitor`; when a
+//! threshold is crossed it cancels the run, so workers wind down exactly as on a
+//! user cancel, and the partial stats carry the `StopReason`.
+
+use crate::{calculate_percentile, ErrorType, LoadTestError, RequestResult};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Minimum time between sliding-window p95 evaluations (sorting the window is not free)
+const LATENCY_CHECK_INTERVAL_MS: f64 = 250.0;
+
+/// Thresholds that abort a running test. Unset thresholds are not checked.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AutoStopConfig {
+    /// Stop when the error rate over the window exceeds this percentage
+    #[serde(default)]
+    pub max_error_rate_percent: Option<f64>,
+    /// Stop when p95 latency over the window exceeds this many milliseconds
+    #[serde(default)]
+    pub max_p95_ms: Option<f64>,
+    /// Stop after this many connection errors in a row
+    #[serde(default)]
+    pub max_consecutive_connection_errors: Option<u32>,
+    /// Sliding window for the error rate and p95 checks
+    #[serde(default = "default_window_secs")]
+    pub window_secs: f64,
+    /// Results required in the window before error rate and p95 are evaluated
+    #[serde(default = "default_min_requests")]
+    pub min_requests: u32,
+}
+
+fn default_window_secs() -> f64 {
+    10.0
+}
+
+fn default_min_requests() -> u32 {
+    20
+}
+
+impl AutoStopConfig {
+    pub(crate) fn validate(&self) -> Result<(), LoadTestError> {
+        if self.window_secs <= 0.0 {
+            return Err(LoadTestError::InvalidConfig("Auto-stop window must be greater than 0 seconds".into()));
+        }
+        if self.max_error_rate_percent.is_some_and(|rate| !(0.0..=100.0).contains(&rate)) {
+            return Err(LoadTestError::InvalidConfig("Auto-stop error rate must be between 0 and 100%".into()));
+        }
+        if self.max_p95_ms.is_some_and(|p95| p95 <= 0.0) {
+            return Err(LoadTestError::InvalidConfig("Auto-stop p95 threshold must be greater than 0 ms".into()));
+        }
+        if self.max_consecutive_connection_errors == Some(0) {
+            return Err(LoadTestError::InvalidConfig(
+                "Auto-stop consecutive connection errors must be greater than 0".into()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Why a test was stopped before issuing all requests
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum StopReason {
+    ErrorRate {
+        error_rate_percent: f64,
+        threshold_percent: f64,
+        window_secs: f64,
+    },
+    LatencyP95 {
+        p95_ms: f64,
+        threshold_ms: f64,
+        window_secs: f64,
+    },
+    ConsecutiveConnectionErrors {
+        count: u32,
+    },
+}
+
+/// A result kept in the sliding window
+struct WindowEntry {
+    timestamp_ms: f64,
+    duration_ms: f64,
+    success: bool,
+}
+
+/// Evaluates the auto-stop thresholds over results in completion order
+pub(crate) struct AutoStopMonitor {
+    config: AutoStopConfig,
+    window: VecDeque<WindowEntry>,
+    window_failures: usize,
+    consecutive_connection_errors: u32,
+    last_latency_check_ms: f64,
+}
+
+impl AutoStopMonitor {
+    pub(crate) fn new(config: AutoStopConfig) -> Self {
+        Self {
+            config,
+            window: VecDeque::new(),
+            window_failures: 0,
+            consecutive_connection_errors: 0,
+            last_latency_check_ms: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Records a result and returns the reason to stop, if a threshold was crossed
+    pub(crate) fn observe(&mut self, result: &RequestResult) -> Option<StopReason> {
+        if result.error_type == ErrorType::Connection {
+            self.consecutive_connection_errors += 1;
+        } else {
+            self.consecutive_connection_errors = 0;
+        }
+        if let Some(max) = self.config.max_consecutive_connection_errors {
+            if self.consecutive_connection_errors >= max {
+                return Some(StopReason::ConsecutiveConnectionErrors { count: self.consecutive_connection_errors });
+            }
+        }
+
+        if self.config.max_error_rate_percent.is_none() && self.config.max_p95_ms.is_none() {
+            return None;
+        }
+
+        // Slide the window forward (results arrive roughly in completion order)
+        self.window.push_back(WindowEntry {
+            timestamp_ms: result.timestamp_ms,
+            duration_ms: result.duration_ms,
+            success: result.success,
+        });
+        if !result.success {
+            self.window_failures += 1;
+        }
+        let window_start_ms = result.timestamp_ms - self.config.window_secs * 1000.0;
+        while self.window.front().is_some_and(|entry| entry.timestamp_ms < window_start_ms) {
+            if let Some(entry) = self.window.pop_front() {
+                if !entry.success {
+                    self.window_failures -= 1;
+                }
+            }
+        }
+        if self.window.len() < self.config.min_requests as usize {
+            return None;
+        }
+
+        if let Some(threshold_percent) = self.config.max_error_rate_percent {
+            let error_rate_percent = self.window_failures as f64 * 100.0 / self.window.len() as f64;
+            if error_rate_percent > threshold_percent {
+                return Some(StopReason::ErrorRate {
+                    error_rate_percent,
+                    threshold_percent,
+                    window_secs: self.config.window_secs,
+                });
+            }
+        }
+
+        if let Some(threshold_ms) = self.config.max_p95_ms {
+            if result.timestamp_ms - self.last_latency_check_ms >= LATENCY_CHECK_INTERVAL_MS {
+                self.last_latency_check_ms = result.timestamp_ms;
+                let mut durations: Vec<f64> = self.window.iter().map(|entry| entry.duration_ms).collect();
+                durations.sort_by(|a, b| a.total_cmp(b));
+                let p95_ms = calculate_percentile(&durations, 95.0);
+                if p95_ms > threshold_ms {
+                    return Some(StopReason::LatencyP95 {
+                        p95_ms,
+                        threshold_ms,
+                        window_secs: self.config.window_secs,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
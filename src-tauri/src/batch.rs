@@ -0,0 +1,314 @@
+//! Sequential batch runs for back-to-back comparisons.
+//!
+//! A batch is a list of configs and/or one base config swept over concurrency, rate limit and
+//! payload size. Variants run one after another through `run_load_test`, separated by a
+//! cooldown, and the batch returns every variant's stats plus a summary row per variant.
+
+use crate::runs;
+use crate::{
+    effective_concurrency, is_valid_rate_limit, run_load_test, HttpMethod, LoadTestConfig, LoadTestError,
+    LoadTestStats, MIN_RATE_LIMIT,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Upper bound on variants in one batch (a sweep multiplies its axes)
+const MAX_BATCH_VARIANTS: usize = 100;
+
+/// Longest pause between variants, in seconds
+pub(crate) const MAX_COOLDOWN_SECS: f64 = 3_600.0;
+
+/// Largest generated body of a payload size sweep
+const MAX_PAYLOAD_SIZE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Field name of the generated JSON payload for payload size sweeps
+const PAYLOAD_FIELD: &str = "data";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BatchConfig {
+    /// Variants to run as-is, in order
+    #[serde(default)]
+    pub configs: Vec<LoadTestConfig>,
+    /// Variants generated from a base config, run after `configs`
+    #[serde(default)]
+    pub sweep: Option<ParameterSweep>,
+    /// Pause between variants so the target can settle, at most 3600 seconds
+    #[serde(default)]
+    pub cooldown_secs: f64,
+}
+
+/// Base config run once per combination of the non-empty axes
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ParameterSweep {
+    pub base: LoadTestConfig,
+    #[serde(default)]
+    pub concurrency: Vec<u32>,
+    /// Rate limits per worker in requests per second (0 = unlimited)
+    #[serde(default)]
+    pub rate_limit: Vec<f64>,
+    /// Request body sizes; each variant sends a generated body of exactly this many bytes
+    /// (at most 16 MiB). The base method must send a body (not GET or HEAD).
+    #[serde(default)]
+    pub payload_size_bytes: Vec<usize>,
+}
+
+/// One entry of the batch: what was run and how it went
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchVariantResult {
+    pub label: String,
+    /// None when the variant failed to start (see `error`)
+    pub stats: Option<LoadTestStats>,
+    pub error: Option<String>,
+}
+
+/// Summary table row comparing one variant with the others
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BatchSummaryRow {
+    pub label: String,
+    pub url: String,
+    /// Workers the variant ran with (at most its request count)
+    pub concurrency: u32,
+    pub rate_limit: f64,
+    #[serde(default)]
+    pub payload_size_bytes: Option<usize>,
+    pub successful_requests: u32,
+    pub failed_requests: u32,
+    pub error_rate_percent: f64,
+    pub requests_per_second: f64,
+    pub avg_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchResult {
+    pub variants: Vec<BatchVariantResult>,
+    pub summary: Vec<BatchSummaryRow>,
+    /// True when a variant or a cooldown was cancelled; the variants after it did not run
+    pub cancelled: bool,
+}
+
+/// Emitted before each variant starts
+#[derive(Debug, Serialize, Clone)]
+struct BatchProgress {
+    /// Zero-based index of the variant about to run
+    index: usize,
+    total: usize,
+    label: String,
+}
+
+/// A variant to run with the sweep values that produced it. Sweep payloads are generated
+/// just before the variant runs, so only one is held in memory at a time.
+struct Variant {
+    label: String,
+    config: LoadTestConfig,
+    payload_size_bytes: Option<usize>,
+}
+
+/// Runs the variants of a batch one after another. Cancelling the running variant (with
+/// `cancel_load_test` or `cancel_run`), or calling `cancel_load_test` during a cooldown, ends
+/// the batch; variants that failed to start are reported with their error and the batch
+/// continues.
+#[tauri::command]
+pub(crate) async fn run_batch(app_handle: AppHandle, batch: BatchConfig) -> Result<BatchResult, LoadTestError> {
+    if !(0.0..=MAX_COOLDOWN_SECS).contains(&batch.cooldown_secs) {
+        return Err(LoadTestError::InvalidConfig(format!(
+            "cooldown_secs must be between 0 and {}",
+            MAX_COOLDOWN_SECS
+        )));
+    }
+    let cancel_epoch = runs::cancel_epoch();
+    let variants = expand(batch.configs, batch.sweep)?;
+    let total = variants.len();
+
+    let mut results = Vec::with_capacity(total);
+    let mut summary = Vec::with_capacity(total);
+    let mut cancelled = false;
+    for (index, mut variant) in variants.into_iter().enumerate() {
+        let settled = if index > 0 && batch.cooldown_secs > 0.0 {
+            runs::cooldown(Duration::from_secs_f64(batch.cooldown_secs), cancel_epoch).await
+        } else {
+            !runs::cancelled_since(cancel_epoch)
+        };
+        if !settled {
+            cancelled = true;
+            break;
+        }
+        if let Some(size) = variant.payload_size_bytes {
+            variant.config.body = Some(sized_payload(size, variant.config.payload_content_type.as_deref()));
+        }
+        let _ = app_handle.emit(
+            "batch-progress",
+            BatchProgress { index, total, label: variant.label.clone() },
+        );
+
+        let mut row = BatchSummaryRow {
+            label: variant.label.clone(),
+            url: variant.config.url.clone(),
+            concurrency: effective_concurrency(variant.config.concurrency, variant.config.num_requests),
+            rate_limit: variant.config.rate_limit,
+            payload_size_bytes: variant.payload_size_bytes,
+            successful_requests: 0,
+            failed_requests: 0,
+            error_rate_percent: 0.0,
+            requests_per_second: 0.0,
+            avg_ms: 0.0,
+            p50_ms: 0.0,
+            p90_ms: 0.0,
+            p95_ms: 0.0,
+            p99_ms: 0.0,
+            max_ms: 0.0,
+            error: None,
+        };
+        match run_load_test(app_handle.clone(), variant.config).await {
+            Ok(stats) => {
+                row.successful_requests = stats.successful_requests;
+                row.failed_requests = stats.failed_requests;
+                row.error_rate_percent = stats.error_rate_percent();
+                row.requests_per_second = stats.requests_per_second;
+                row.avg_ms = stats.avg_response_time_ms;
+                row.p50_ms = stats.percentiles.p50;
+                row.p90_ms = stats.percentiles.p90;
+                row.p95_ms = stats.percentiles.p95;
+                row.p99_ms = stats.percentiles.p99;
+                row.max_ms = stats.max_response_time_ms;
+                cancelled = stats.cancelled;
+                results.push(BatchVariantResult { label: variant.label, stats: Some(stats), error: None });
+            }
+            Err(e) => {
+                row.error = Some(e.to_string());
+                results.push(BatchVariantResult { label: variant.label, stats: None, error: Some(e.to_string()) });
+            }
+        }
+        summary.push(row);
+        if cancelled {
+            break;
+        }
+    }
+
+    Ok(BatchResult { variants: results, summary, cancelled })
+}
+
+/// Expands explicit configs and the sweep into the ordered list of variants
+fn expand(configs: Vec<LoadTestConfig>, sweep: Option<ParameterSweep>) -> Result<Vec<Variant>, LoadTestError> {
+    let mut variants: Vec<Variant> = configs
+        .into_iter()
+        .enumerate()
+        .map(|(i, config)| Variant {
+            label: format!(
+                "#{} {} c={}",
+                i + 1,
+                config.url,
+                effective_concurrency(config.concurrency, config.num_requests)
+            ),
+            config,
+            payload_size_bytes: None,
+        })
+        .collect();
+
+    if let Some(sweep) = sweep {
+        if sweep.concurrency.contains(&0) {
+            return Err(LoadTestError::InvalidConfig("Sweep concurrency values must be greater than 0".into()));
+        }
+        if sweep.rate_limit.iter().any(|rate| !is_valid_rate_limit(*rate)) {
+            return Err(LoadTestError::InvalidConfig(format!(
+                "Sweep rate limits must be 0 (unlimited) or at least {} req/s",
+                MIN_RATE_LIMIT
+            )));
+        }
+        if sweep.payload_size_bytes.iter().any(|&size| size > MAX_PAYLOAD_SIZE_BYTES) {
+            return Err(LoadTestError::InvalidConfig(format!(
+                "Sweep payload sizes must be at most {} bytes",
+                MAX_PAYLOAD_SIZE_BYTES
+            )));
+        }
+        if !sweep.payload_size_bytes.is_empty() && sweep.base.form_fields.is_some() {
+            return Err(LoadTestError::InvalidConfig(
+                "Payload size sweeps cannot be combined with multipart form fields".into()
+            ));
+        }
+        if !sweep.payload_size_bytes.is_empty() && matches!(sweep.base.method, HttpMethod::GET | HttpMethod::HEAD) {
+            return Err(LoadTestError::InvalidConfig(format!(
+                "Payload size sweeps need a method that sends a body, not {:?}",
+                sweep.base.method
+            )));
+        }
+
+        let combinations = sweep.concurrency.len().max(1)
+            * sweep.rate_limit.len().max(1)
+            * sweep.payload_size_bytes.len().max(1);
+        if variants.len() + combinations > MAX_BATCH_VARIANTS {
+            return Err(LoadTestError::InvalidConfig(
+                format!("A batch can run at most {} variants", MAX_BATCH_VARIANTS)
+            ));
+        }
+
+        // An empty axis keeps the base value
+        let concurrencies: Vec<Option<u32>> = axis(&sweep.concurrency);
+        let rate_limits: Vec<Option<f64>> = axis(&sweep.rate_limit);
+        let payload_sizes: Vec<Option<usize>> = axis(&sweep.payload_size_bytes);
+        for concurrency in &concurrencies {
+            for rate_limit in &rate_limits {
+                for payload_size in &payload_sizes {
+                    let mut config = sweep.base.clone();
+                    let mut parts = Vec::new();
+                    if let Some(concurrency) = *concurrency {
+                        config.concurrency = concurrency;
+                        parts.push(format!("concurrency {}", effective_concurrency(concurrency, config.num_requests)));
+                    }
+                    if let Some(rate_limit) = *rate_limit {
+                        config.rate_limit = rate_limit;
+                        parts.push(if rate_limit > 0.0 {
+                            format!("rate {} req/s", rate_limit)
+                        } else {
+                            "rate unlimited".to_string()
+                        });
+                    }
+                    if let Some(size) = *payload_size {
+                        parts.push(format!("payload {} B", size));
+                    }
+                    let label = if parts.is_empty() { config.url.clone() } else { parts.join(", ") };
+                    variants.push(Variant { label, config, payload_size_bytes: *payload_size });
+                }
+            }
+        }
+    }
+
+    if variants.is_empty() {
+        return Err(LoadTestError::InvalidConfig("A batch needs at least one config or a sweep".into()));
+    }
+    if variants.len() > MAX_BATCH_VARIANTS {
+        return Err(LoadTestError::InvalidConfig(
+            format!("A batch can run at most {} variants", MAX_BATCH_VARIANTS)
+        ));
+    }
+    Ok(variants)
+}
+
+/// Values of a sweep axis, or a single "keep the base value" entry when the axis is empty
+fn axis<T: Copy>(values: &[T]) -> Vec<Option<T>> {
+    if values.is_empty() {
+        vec![None]
+    } else {
+        values.iter().copied().map(Some).collect()
+    }
+}
+
+/// Generates a body of exactly `size` bytes: a JSON object for JSON content types (when
+/// large enough to hold one), filler text otherwise
+fn sized_payload(size: usize, content_type: Option<&str>) -> String {
+    let is_json = content_type.is_some_and(|content_type| content_type.contains("json"));
+    // {"data":""}
+    let json_overhead = PAYLOAD_FIELD.len() + 7;
+    if is_json && size >= json_overhead {
+        format!("{{\"{}\":\"{}\"}}", PAYLOAD_FIELD, "x".repeat(size - json_overhead))
+    } else {
+        "x".repeat(size)
+    }
+}
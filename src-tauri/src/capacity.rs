@@ -0,0 +1,309 @@
+//! Capacity (breakpoint) search: the highest arrival rate the target sustains within SLOs.
+//!
+//! Each step runs the base config through `run_load_test` at a target total rate, spread over
+//! the base concurrency as a per-worker rate limit and sized to last the hold window. A step
+//! passes when p99 and error rate stay within limits and the achieved rate keeps up with the
+//! target. Rates are either stepped up linearly until the first failure or binary searched.
+
+use crate::batch::MAX_COOLDOWN_SECS;
+use crate::runs;
+use crate::{run_load_test, LoadTestConfig, LoadTestError, RunId, MIN_RATE_LIMIT};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Upper bound on steps in one search
+const MAX_CAPACITY_STEPS: usize = 50;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum SearchStrategy {
+    /// start, start + step, ... until the first failing step or `max_rps`
+    #[default]
+    Step,
+    /// Bisect between `start_rps` and `max_rps` until within `precision_rps`
+    Binary,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CapacitySearchConfig {
+    /// Config run at every step; its concurrency is the number of workers sharing the rate
+    pub base: LoadTestConfig,
+    #[serde(default)]
+    pub strategy: SearchStrategy,
+    /// Total requests per second of the first step
+    pub start_rps: f64,
+    /// Highest total rate tried
+    pub max_rps: f64,
+    /// Rate increment of the step strategy
+    #[serde(default)]
+    pub step_rps: f64,
+    /// Binary search stops once the passing and failing rates are this close
+    #[serde(default = "default_precision_rps")]
+    pub precision_rps: f64,
+    /// How long each step sends traffic
+    #[serde(default = "default_hold_secs")]
+    pub hold_secs: f64,
+    /// Pause between steps so the target can recover, at most 3600 seconds
+    #[serde(default)]
+    pub cooldown_secs: f64,
+    /// A step fails when its p99 exceeds this
+    pub max_p99_ms: f64,
+    /// A step fails when its error rate exceeds this percentage
+    #[serde(default = "default_max_error_rate_percent")]
+    pub max_error_rate_percent: f64,
+    /// A step fails when the achieved rate falls below this fraction of the target
+    #[serde(default = "default_min_achieved_ratio")]
+    pub min_achieved_ratio: f64,
+}
+
+fn default_precision_rps() -> f64 {
+    1.0
+}
+
+fn default_hold_secs() -> f64 {
+    10.0
+}
+
+fn default_max_error_rate_percent() -> f64 {
+    1.0
+}
+
+fn default_min_achieved_ratio() -> f64 {
+    0.9
+}
+
+/// One point of the capacity curve
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CapacityStep {
+    /// None when the step's run failed to start (see `failure`)
+    #[serde(default)]
+    pub run_id: Option<RunId>,
+    pub target_rps: f64,
+    pub achieved_rps: f64,
+    pub requests: u32,
+    pub p50_ms: f64,
+    pub p99_ms: f64,
+    pub error_rate_percent: f64,
+    pub passed: bool,
+    /// Why the step failed, e.g. "p99 512.3 ms > 300 ms", or the error that kept it from running
+    #[serde(default)]
+    pub failure: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CapacityResult {
+    /// Steps in the order they ran
+    pub steps: Vec<CapacityStep>,
+    /// Highest target rate that passed (None when the first step failed)
+    pub breakpoint_rps: Option<f64>,
+    /// Rate achieved at the breakpoint
+    pub max_sustainable_rps: Option<f64>,
+    /// Lowest target rate that failed (None when every step passed)
+    pub first_failing_rps: Option<f64>,
+    /// True when a step or a cooldown was cancelled; the search ended early
+    pub cancelled: bool,
+}
+
+impl CapacitySearchConfig {
+    fn validate(&self) -> Result<(), LoadTestError> {
+        if self.base.concurrency == 0 {
+            return Err(LoadTestError::InvalidConfig(
+                "Capacity search needs a base concurrency greater than 0".into()
+            ));
+        }
+        if !self.start_rps.is_finite() || self.start_rps <= 0.0 {
+            return Err(LoadTestError::InvalidConfig("start_rps must be greater than 0".into()));
+        }
+        if self.start_rps / f64::from(self.base.concurrency) < MIN_RATE_LIMIT {
+            return Err(LoadTestError::InvalidConfig(format!(
+                "start_rps must be at least {} req/s per worker of the base concurrency",
+                MIN_RATE_LIMIT
+            )));
+        }
+        if !self.max_rps.is_finite() || self.max_rps < self.start_rps {
+            return Err(LoadTestError::InvalidConfig("max_rps must be at least start_rps".into()));
+        }
+        match self.strategy {
+            SearchStrategy::Step if !self.step_rps.is_finite() || self.step_rps <= 0.0 => {
+                return Err(LoadTestError::InvalidConfig("step_rps must be greater than 0".into()));
+            }
+            SearchStrategy::Binary if !self.precision_rps.is_finite() || self.precision_rps <= 0.0 => {
+                return Err(LoadTestError::InvalidConfig("precision_rps must be greater than 0".into()));
+            }
+            _ => {}
+        }
+        if !self.hold_secs.is_finite() || self.hold_secs <= 0.0 {
+            return Err(LoadTestError::InvalidConfig("hold_secs must be greater than 0".into()));
+        }
+        if !(0.0..=MAX_COOLDOWN_SECS).contains(&self.cooldown_secs) {
+            return Err(LoadTestError::InvalidConfig(format!(
+                "cooldown_secs must be between 0 and {}",
+                MAX_COOLDOWN_SECS
+            )));
+        }
+        if !self.max_p99_ms.is_finite() || self.max_p99_ms <= 0.0 {
+            return Err(LoadTestError::InvalidConfig("max_p99_ms must be greater than 0".into()));
+        }
+        if !(0.0..=100.0).contains(&self.max_error_rate_percent) {
+            return Err(LoadTestError::InvalidConfig("max_error_rate_percent must be between 0 and 100".into()));
+        }
+        if !(0.0..=1.0).contains(&self.min_achieved_ratio) {
+            return Err(LoadTestError::InvalidConfig("min_achieved_ratio must be between 0 and 1".into()));
+        }
+        Ok(())
+    }
+}
+
+/// Runs steps at increasing (or bisected) arrival rates and reports the breakpoint.
+/// Each finished step is emitted as "capacity-step". Cancelling the running step, or calling
+/// `cancel_load_test` during a cooldown, ends the search; so does a step that fails to start,
+/// which is recorded with its error.
+#[tauri::command]
+pub(crate) async fn run_capacity_search(
+    app_handle: AppHandle,
+    search: CapacitySearchConfig,
+) -> Result<CapacityResult, LoadTestError> {
+    search.validate()?;
+
+    let cancel_epoch = runs::cancel_epoch();
+    let mut searcher =
+        Searcher { app_handle, search, steps: Vec::new(), cancelled: false, aborted: false, cancel_epoch };
+    let config = searcher.search.clone();
+    match config.strategy {
+        SearchStrategy::Step => {
+            for step in 0.. {
+                let rate = config.start_rps + step as f64 * config.step_rps;
+                if rate > config.max_rps || !searcher.run_step(rate).await {
+                    break;
+                }
+            }
+        }
+        SearchStrategy::Binary => {
+            // Bisect only when the start rate passes and the max rate fails
+            if searcher.run_step(config.start_rps).await && !searcher.run_step(config.max_rps).await {
+                // `low` always passed, `high` always failed
+                let (mut low, mut high) = (config.start_rps, config.max_rps);
+                while high - low > config.precision_rps && !searcher.is_done() {
+                    let mid = (low + high) / 2.0;
+                    if searcher.run_step(mid).await {
+                        low = mid;
+                    } else {
+                        high = mid;
+                    }
+                }
+            }
+        }
+    }
+
+    let Searcher { steps, cancelled, .. } = searcher;
+    let breakpoint = steps
+        .iter()
+        .filter(|step| step.passed)
+        .max_by(|a, b| a.target_rps.total_cmp(&b.target_rps));
+    // Steps that failed to start say nothing about the rate
+    let first_failing_rps = steps
+        .iter()
+        .filter(|step| !step.passed && step.run_id.is_some())
+        .map(|step| step.target_rps)
+        .min_by(|a, b| a.total_cmp(b));
+    Ok(CapacityResult {
+        breakpoint_rps: breakpoint.map(|step| step.target_rps),
+        max_sustainable_rps: breakpoint.map(|step| step.achieved_rps),
+        first_failing_rps,
+        cancelled,
+        steps,
+    })
+}
+
+struct Searcher {
+    app_handle: AppHandle,
+    search: CapacitySearchConfig,
+    steps: Vec<CapacityStep>,
+    cancelled: bool,
+    /// A step failed to start; the search stops with the curve so far
+    aborted: bool,
+    /// Taken when the search started, to notice a cancel between steps
+    cancel_epoch: u64,
+}
+
+impl Searcher {
+    fn is_done(&self) -> bool {
+        self.cancelled || self.aborted || self.steps.len() >= MAX_CAPACITY_STEPS
+    }
+
+    /// Runs one step at `target_rps`. Returns whether it passed; false once the search
+    /// should stop (failed, cancelled, failed to start, or out of steps).
+    async fn run_step(&mut self, target_rps: f64) -> bool {
+        if self.is_done() {
+            return false;
+        }
+        let settled = if !self.steps.is_empty() && self.search.cooldown_secs > 0.0 {
+            runs::cooldown(Duration::from_secs_f64(self.search.cooldown_secs), self.cancel_epoch).await
+        } else {
+            !runs::cancelled_since(self.cancel_epoch)
+        };
+        if !settled {
+            self.cancelled = true;
+            return false;
+        }
+
+        let mut config = self.search.base.clone();
+        config.rate_limit = target_rps / config.concurrency as f64;
+        config.num_requests = ((target_rps * self.search.hold_secs).ceil() as u32).max(config.concurrency);
+        let stats = match run_load_test(self.app_handle.clone(), config).await {
+            Ok(stats) => stats,
+            Err(e) => {
+                self.aborted = true;
+                let step = CapacityStep {
+                    run_id: None,
+                    target_rps,
+                    achieved_rps: 0.0,
+                    requests: 0,
+                    p50_ms: 0.0,
+                    p99_ms: 0.0,
+                    error_rate_percent: 0.0,
+                    passed: false,
+                    failure: Some(e.to_string()),
+                };
+                let _ = self.app_handle.emit("capacity-step", &step);
+                self.steps.push(step);
+                return false;
+            }
+        };
+
+        let error_rate_percent = stats.error_rate_percent();
+        let mut failures = Vec::new();
+        if stats.percentiles.p99 > self.search.max_p99_ms {
+            failures.push(format!("p99 {:.1} ms > {} ms", stats.percentiles.p99, self.search.max_p99_ms));
+        }
+        if error_rate_percent > self.search.max_error_rate_percent {
+            failures.push(format!("error rate {:.2}% > {}%", error_rate_percent, self.search.max_error_rate_percent));
+        }
+        if stats.requests_per_second < target_rps * self.search.min_achieved_ratio {
+            failures.push(format!("achieved {:.1} req/s of {:.1} target", stats.requests_per_second, target_rps));
+        }
+        if let Some(reason) = &stats.stop_reason {
+            failures.push(format!("auto-stopped: {:?}", reason));
+        }
+        self.cancelled = stats.cancelled;
+
+        let step = CapacityStep {
+            run_id: Some(stats.run_id),
+            target_rps,
+            achieved_rps: stats.requests_per_second,
+            requests: stats.successful_requests + stats.failed_requests,
+            p50_ms: stats.percentiles.p50,
+            p99_ms: stats.percentiles.p99,
+            error_rate_percent,
+            // A cancelled step is partial and says nothing about the rate
+            passed: failures.is_empty() && !self.cancelled,
+            failure: (!failures.is_empty()).then(|| failures.join(", ")),
+        };
+        let _ = self.app_handle.emit("capacity-step", &step);
+        let passed = step.passed;
+        if !self.cancelled {
+            self.steps.push(step);
+        }
+        passed
+    }
+}
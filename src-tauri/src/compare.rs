@@ -0,0 +1,632 @@
+//! Comparison of two saved runs for regression detection.
+//!
+//! Aggregates of a baseline and a candidate run are diffed metric by metric, and each change
+//! is tested for significance: latency distributions with a Mann-Whitney U test plus bootstrap
+//! confidence intervals of the change in mean and percentiles (raw results required),
+//! throughput with a bootstrap over per-window rates, and error rates with a two-proportion
+//! z-test. A metric is flagged when its change is significant and at least
+//! `min_change_percent`. Runs target a single endpoint, so the metrics are that endpoint's.
+
+use crate::history::{self, SavedRun, SavedRunSummary};
+use crate::{calculate_percentile, LoadTestError, LoadTestStats};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+/// Latency samples per run used for bootstrapping; larger runs are subsampled
+const MAX_BOOTSTRAP_SAMPLES: usize = 10_000;
+
+const MAX_BOOTSTRAP_RESAMPLES: u32 = 10_000;
+
+/// Fixed seed so comparing the same runs twice gives the same report
+const BOOTSTRAP_SEED: u64 = 0x5eed;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CompareOptions {
+    /// Significance level of the tests
+    #[serde(default = "default_alpha")]
+    pub alpha: f64,
+    /// Changes smaller than this percentage of the baseline are not flagged, even when significant
+    #[serde(default = "default_min_change_percent")]
+    pub min_change_percent: f64,
+    /// Resamples per bootstrap confidence interval (0 disables the intervals)
+    #[serde(default = "default_bootstrap_resamples")]
+    pub bootstrap_resamples: u32,
+}
+
+fn default_alpha() -> f64 {
+    0.05
+}
+
+fn default_min_change_percent() -> f64 {
+    5.0
+}
+
+fn default_bootstrap_resamples() -> u32 {
+    1000
+}
+
+impl Default for CompareOptions {
+    fn default() -> Self {
+        Self {
+            alpha: default_alpha(),
+            min_change_percent: default_min_change_percent(),
+            bootstrap_resamples: default_bootstrap_resamples(),
+        }
+    }
+}
+
+impl CompareOptions {
+    fn validate(&self) -> Result<(), LoadTestError> {
+        if !(self.alpha > 0.0 && self.alpha < 1.0) {
+            return Err(LoadTestError::InvalidConfig("alpha must be between 0 and 1".into()));
+        }
+        if !self.min_change_percent.is_finite() || self.min_change_percent < 0.0 {
+            return Err(LoadTestError::InvalidConfig("min_change_percent must be 0 or a positive number".into()));
+        }
+        if self.bootstrap_resamples > MAX_BOOTSTRAP_RESAMPLES {
+            return Err(LoadTestError::InvalidConfig(
+                format!("bootstrap_resamples must be at most {}", MAX_BOOTSTRAP_RESAMPLES)
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ChangeVerdict {
+    Improved,
+    Unchanged,
+    Regressed,
+}
+
+/// Change of one metric from the baseline to the candidate
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MetricComparison {
+    /// Metric name, e.g. "p99_ms" or "requests_per_second"
+    pub metric: String,
+    pub baseline: f64,
+    pub candidate: f64,
+    /// candidate - baseline
+    pub change: f64,
+    /// Change relative to the baseline, None when the baseline is 0
+    pub change_percent: Option<f64>,
+    /// Bootstrap confidence interval of `change` at 1 - alpha
+    pub ci_low: Option<f64>,
+    pub ci_high: Option<f64>,
+    /// Whether the change is statistically significant; None when no test applies
+    pub significant: Option<bool>,
+    /// Unchanged unless the change is both significant and large enough (untested changes included)
+    pub verdict: ChangeVerdict,
+}
+
+/// Mann-Whitney U test of candidate against baseline latencies (normal approximation,
+/// corrected for ties)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MannWhitneyTest {
+    /// U statistic of the candidate sample
+    pub u: f64,
+    pub z: f64,
+    /// Two-sided p-value
+    pub p_value: f64,
+    /// Probability that a candidate latency exceeds a baseline latency (ties count half);
+    /// 0.5 means no shift
+    pub probability_candidate_slower: f64,
+    pub significant: bool,
+}
+
+/// Two-proportion z-test of the error rates
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProportionTest {
+    pub z: f64,
+    /// Two-sided p-value
+    pub p_value: f64,
+    pub significant: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StatusCodeComparison {
+    pub code: u16,
+    pub baseline: u32,
+    pub candidate: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ComparisonReport {
+    pub baseline: SavedRunSummary,
+    pub candidate: SavedRunSummary,
+    pub options: CompareOptions,
+    /// Whether both runs target the same method and URL
+    pub same_endpoint: bool,
+    pub metrics: Vec<MetricComparison>,
+    /// Present when both runs were saved with raw results
+    pub latency_test: Option<MannWhitneyTest>,
+    /// Present when both runs completed requests
+    pub error_rate_test: Option<ProportionTest>,
+    pub status_codes: Vec<StatusCodeComparison>,
+    /// Names of the metrics that regressed
+    pub regressions: Vec<String>,
+    pub regressed: bool,
+    /// Caveats, e.g. missing raw results or partial runs
+    pub notes: Vec<String>,
+}
+
+/// Compares a candidate run with a baseline run, both loaded from history
+#[tauri::command]
+pub(crate) async fn compare_runs(
+    app_handle: AppHandle,
+    baseline_id: i64,
+    candidate_id: i64,
+    options: Option<CompareOptions>,
+) -> Result<ComparisonReport, LoadTestError> {
+    let options = options.unwrap_or_default();
+    options.validate()?;
+    let baseline = history::load_saved_run(app_handle.clone(), baseline_id, Some(true)).await?;
+    let candidate = history::load_saved_run(app_handle, candidate_id, Some(true)).await?;
+    // Bootstrapping is CPU-bound
+    tokio::task::spawn_blocking(move || compare(baseline, candidate, options))
+        .await
+        .map_err(|e| LoadTestError::Internal(format!("Task join error: {}", e)))
+}
+
+/// Statistic of a sample, computed on a sorted resample
+#[derive(Clone, Copy)]
+enum Statistic {
+    Mean,
+    Percentile(f64),
+}
+
+impl Statistic {
+    fn of(self, sorted: &[f64]) -> f64 {
+        match self {
+            Statistic::Mean if sorted.is_empty() => 0.0,
+            Statistic::Mean => sorted.iter().sum::<f64>() / sorted.len() as f64,
+            Statistic::Percentile(percentile) => calculate_percentile(sorted, percentile),
+        }
+    }
+}
+
+fn compare(baseline: SavedRun, candidate: SavedRun, options: CompareOptions) -> ComparisonReport {
+    let mut rng = StdRng::seed_from_u64(BOOTSTRAP_SEED);
+    let (base, cand) = (&baseline.stats, &candidate.stats);
+    let mut notes = Vec::new();
+
+    let same_endpoint = baseline.summary.url == candidate.summary.url && baseline.summary.method == candidate.summary.method;
+    if !same_endpoint {
+        notes.push(format!(
+            "The runs target different endpoints ({:?} {} vs {:?} {})",
+            baseline.summary.method, baseline.summary.url, candidate.summary.method, candidate.summary.url
+        ));
+    }
+    for run in [&baseline, &candidate] {
+        if run.stats.cancelled || run.stats.stop_reason.is_some() {
+            notes.push(format!("Run {} ended early; its stats are partial", run.summary.id));
+        }
+    }
+
+    // Latency: rank test on the full samples, bootstrap intervals on (subsampled) copies
+    let latencies = match (measured_latencies(base), measured_latencies(cand)) {
+        (Some(baseline_latencies), Some(candidate_latencies)) => Some((baseline_latencies, candidate_latencies)),
+        _ => {
+            for run in [&baseline, &candidate] {
+                if !run.summary.has_results {
+                    notes.push(format!(
+                        "Run {} was saved without raw results; latency changes are not tested for significance",
+                        run.summary.id
+                    ));
+                }
+            }
+            None
+        }
+    };
+    let latency_test = latencies
+        .as_ref()
+        .and_then(|(baseline_latencies, candidate_latencies)| mann_whitney(baseline_latencies, candidate_latencies, options.alpha));
+
+    let latency_metrics = [
+        ("avg_response_time_ms", base.avg_response_time_ms, cand.avg_response_time_ms, Statistic::Mean),
+        ("p50_ms", base.percentiles.p50, cand.percentiles.p50, Statistic::Percentile(50.0)),
+        ("p90_ms", base.percentiles.p90, cand.percentiles.p90, Statistic::Percentile(90.0)),
+        ("p95_ms", base.percentiles.p95, cand.percentiles.p95, Statistic::Percentile(95.0)),
+        ("p99_ms", base.percentiles.p99, cand.percentiles.p99, Statistic::Percentile(99.0)),
+    ];
+    let statistics: Vec<Statistic> = latency_metrics.iter().map(|metric| metric.3).collect();
+    let latency_intervals = match &latencies {
+        Some((baseline_latencies, candidate_latencies)) if options.bootstrap_resamples > 0 => bootstrap_intervals(
+            &subsample(baseline_latencies, &mut rng),
+            &subsample(candidate_latencies, &mut rng),
+            &statistics,
+            &options,
+            &mut rng,
+        ),
+        _ => None,
+    };
+
+    let mut metrics = Vec::new();
+    for (i, (name, baseline_value, candidate_value, _)) in latency_metrics.into_iter().enumerate() {
+        let interval = latency_intervals.as_ref().map(|intervals| intervals[i]);
+        // Without intervals the rank test stands in for every latency metric
+        let significant = interval
+            .map(|(low, high)| low > 0.0 || high < 0.0)
+            .or(latency_test.as_ref().map(|test| test.significant));
+        metrics.push(metric(name, baseline_value, candidate_value, interval, significant, true, &options));
+    }
+
+    // Throughput: bootstrap the mean of the per-window rates
+    let (baseline_rates, candidate_rates) = (window_rates(base), window_rates(cand));
+    let rate_interval = if options.bootstrap_resamples > 0 && baseline_rates.len() > 1 && candidate_rates.len() > 1 {
+        bootstrap_intervals(&baseline_rates, &candidate_rates, &[Statistic::Mean], &options, &mut rng)
+            .map(|intervals| intervals[0])
+    } else {
+        None
+    };
+    metrics.push(metric(
+        "requests_per_second",
+        base.requests_per_second,
+        cand.requests_per_second,
+        rate_interval,
+        rate_interval.map(|(low, high)| low > 0.0 || high < 0.0),
+        false,
+        &options,
+    ));
+
+    let error_rate_test = proportion_test(
+        (base.failed_requests, base.successful_requests + base.failed_requests),
+        (cand.failed_requests, cand.successful_requests + cand.failed_requests),
+        options.alpha,
+    );
+    metrics.push(metric(
+        "error_rate_percent",
+        base.error_rate_percent(),
+        cand.error_rate_percent(),
+        None,
+        error_rate_test.as_ref().map(|test| test.significant),
+        true,
+        &options,
+    ));
+
+    let untested: Vec<&str> = metrics
+        .iter()
+        .filter(|metric| {
+            let large = metric.change_percent.is_none_or(|percent| percent.abs() >= options.min_change_percent);
+            metric.significant.is_none() && metric.change != 0.0 && large
+        })
+        .map(|metric| metric.metric.as_str())
+        .collect();
+    if !untested.is_empty() {
+        notes.push(format!(
+            "Changes in {} could not be tested for significance and are not flagged",
+            untested.join(", ")
+        ));
+    }
+
+    let mut status_counts: BTreeMap<u16, (u32, u32)> = BTreeMap::new();
+    for status in &base.status_codes {
+        status_counts.entry(status.code).or_default().0 += status.count;
+    }
+    for status in &cand.status_codes {
+        status_counts.entry(status.code).or_default().1 += status.count;
+    }
+    let status_codes = status_counts
+        .into_iter()
+        .map(|(code, (baseline, candidate))| StatusCodeComparison { code, baseline, candidate })
+        .collect();
+
+    let regressions: Vec<String> = metrics
+        .iter()
+        .filter(|metric| metric.verdict == ChangeVerdict::Regressed)
+        .map(|metric| metric.metric.clone())
+        .collect();
+    ComparisonReport {
+        baseline: baseline.summary,
+        candidate: candidate.summary,
+        options,
+        same_endpoint,
+        metrics,
+        latency_test,
+        error_rate_test,
+        status_codes,
+        regressed: !regressions.is_empty(),
+        regressions,
+        notes,
+    }
+}
+
+fn metric(
+    name: &str,
+    baseline: f64,
+    candidate: f64,
+    interval: Option<(f64, f64)>,
+    significant: Option<bool>,
+    higher_is_worse: bool,
+    options: &CompareOptions,
+) -> MetricComparison {
+    let change = candidate - baseline;
+    let change_percent = if baseline != 0.0 {
+        Some(change * 100.0 / baseline)
+    } else if change == 0.0 {
+        Some(0.0)
+    } else {
+        None
+    };
+    // Any change from a zero baseline (e.g. the first errors) counts as large
+    let large = change_percent.is_none_or(|percent| percent.abs() >= options.min_change_percent);
+    // Only significant changes are flagged; untested ones (significant == None) stay Unchanged
+    let verdict = if change == 0.0 || !large || significant != Some(true) {
+        ChangeVerdict::Unchanged
+    } else if (change > 0.0) == higher_is_worse {
+        ChangeVerdict::Regressed
+    } else {
+        ChangeVerdict::Improved
+    };
+    MetricComparison {
+        metric: name.to_string(),
+        baseline,
+        candidate,
+        change,
+        change_percent,
+        ci_low: interval.map(|(low, _)| low),
+        ci_high: interval.map(|(_, high)| high),
+        significant,
+        verdict,
+    }
+}
+
+/// Sorted latencies of the measured (non warm-up) results; None without raw results
+fn measured_latencies(stats: &LoadTestStats) -> Option<Vec<f64>> {
+    let mut latencies: Vec<f64> = stats.results.iter().filter(|r| !r.warmup).map(|r| r.duration_ms).collect();
+    if latencies.is_empty() {
+        return None;
+    }
+    latencies.sort_by(|a, b| a.total_cmp(b));
+    Some(latencies)
+}
+
+/// Request rate of each complete, measured window
+fn window_rates(stats: &LoadTestStats) -> Vec<f64> {
+    stats
+        .window_stats
+        .iter()
+        .filter(|window| !window.warmup && window.end_secs <= stats.total_time_secs)
+        .map(|window| window.requests as f64 / (window.end_secs - window.start_secs))
+        .collect()
+}
+
+/// Random sample of at most `MAX_BOOTSTRAP_SAMPLES` values
+fn subsample(values: &[f64], rng: &mut StdRng) -> Vec<f64> {
+    if values.len() <= MAX_BOOTSTRAP_SAMPLES {
+        return values.to_vec();
+    }
+    (0..MAX_BOOTSTRAP_SAMPLES).map(|_| values[rng.gen_range(0..values.len())]).collect()
+}
+
+/// Percentile bootstrap confidence intervals of candidate - baseline for each statistic
+fn bootstrap_intervals(
+    baseline: &[f64],
+    candidate: &[f64],
+    statistics: &[Statistic],
+    options: &CompareOptions,
+    rng: &mut StdRng,
+) -> Option<Vec<(f64, f64)>> {
+    if baseline.is_empty() || candidate.is_empty() {
+        return None;
+    }
+    let resamples = options.bootstrap_resamples as usize;
+    let mut differences = vec![Vec::with_capacity(resamples); statistics.len()];
+    let mut baseline_resample = vec![0.0; baseline.len()];
+    let mut candidate_resample = vec![0.0; candidate.len()];
+    for _ in 0..resamples {
+        for (source, resample) in [(baseline, &mut baseline_resample), (candidate, &mut candidate_resample)] {
+            for value in resample.iter_mut() {
+                *value = source[rng.gen_range(0..source.len())];
+            }
+            resample.sort_by(|a, b| a.total_cmp(b));
+        }
+        for (i, statistic) in statistics.iter().enumerate() {
+            differences[i].push(statistic.of(&candidate_resample) - statistic.of(&baseline_resample));
+        }
+    }
+    Some(
+        differences
+            .into_iter()
+            .map(|mut difference| {
+                difference.sort_by(|a, b| a.total_cmp(b));
+                (
+                    calculate_percentile(&difference, options.alpha / 2.0 * 100.0),
+                    calculate_percentile(&difference, (1.0 - options.alpha / 2.0) * 100.0),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Mann-Whitney U test on sorted samples
+fn mann_whitney(baseline: &[f64], candidate: &[f64], alpha: f64) -> Option<MannWhitneyTest> {
+    if baseline.is_empty() || candidate.is_empty() {
+        return None;
+    }
+    let (n1, n2) = (baseline.len() as f64, candidate.len() as f64);
+
+    // Merge the samples in order, giving tied values their average rank
+    let (mut i, mut j) = (0, 0);
+    let mut next_rank = 1.0;
+    let mut candidate_rank_sum = 0.0;
+    let mut tie_correction = 0.0;
+    loop {
+        let value = match (baseline.get(i), candidate.get(j)) {
+            (Some(&a), Some(&b)) => a.min(b),
+            (Some(&a), None) => a,
+            (None, Some(&b)) => b,
+            (None, None) => break,
+        };
+        let start = (i, j);
+        while baseline.get(i) == Some(&value) {
+            i += 1;
+        }
+        while candidate.get(j) == Some(&value) {
+            j += 1;
+        }
+        let ties = (i - start.0 + j - start.1) as f64;
+        candidate_rank_sum += (j - start.1) as f64 * (next_rank + (ties - 1.0) / 2.0);
+        tie_correction += ties * ties * ties - ties;
+        next_rank += ties;
+    }
+
+    let u = candidate_rank_sum - n2 * (n2 + 1.0) / 2.0;
+    let n = n1 + n2;
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - tie_correction / (n * (n - 1.0)));
+    let z = if variance > 0.0 { (u - n1 * n2 / 2.0) / variance.sqrt() } else { 0.0 };
+    let p_value = two_sided_p_value(z);
+    Some(MannWhitneyTest {
+        u,
+        z,
+        p_value,
+        probability_candidate_slower: u / (n1 * n2),
+        significant: variance > 0.0 && p_value < alpha,
+    })
+}
+
+/// Two-proportion z-test on the failed share of measured requests, given as
+/// (failed, completed) counts
+fn proportion_test(baseline: (u32, u32), candidate: (u32, u32), alpha: f64) -> Option<ProportionTest> {
+    let (failed1, n1) = (baseline.0 as f64, baseline.1 as f64);
+    let (failed2, n2) = (candidate.0 as f64, candidate.1 as f64);
+    if n1 == 0.0 || n2 == 0.0 {
+        return None;
+    }
+    let (p1, p2) = (failed1 / n1, failed2 / n2);
+    let pooled = (failed1 + failed2) / (n1 + n2);
+    let standard_error = (pooled * (1.0 - pooled) * (1.0 / n1 + 1.0 / n2)).sqrt();
+    let z = if standard_error > 0.0 { (p2 - p1) / standard_error } else { 0.0 };
+    let p_value = two_sided_p_value(z);
+    Some(ProportionTest { z, p_value, significant: standard_error > 0.0 && p_value < alpha })
+}
+
+fn two_sided_p_value(z: f64) -> f64 {
+    erfc(z.abs() / std::f64::consts::SQRT_2).min(1.0)
+}
+
+/// Complementary error function (Numerical Recipes `erfcc`, fractional error below 1.2e-7)
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let polynomial = -1.26551223
+        + t * (1.00002368
+            + t * (0.37409196
+                + t * (0.09678418
+                    + t * (-0.18628806
+                        + t * (0.27886807 + t * (-1.13520398 + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))));
+    let result = t * (-z * z + polynomial).exp();
+    if x >= 0.0 {
+        result
+    } else {
+        2.0 - result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{} differs from {} by more than {}",
+            actual,
+            expected,
+            tolerance
+        );
+    }
+
+    #[test]
+    fn erfc_matches_reference_values() {
+        for (x, expected) in [
+            (0.0, 1.0),
+            (0.5, 0.4795001221869535),
+            (1.0, 0.15729920705028513),
+            (2.0, 0.004677734981047265),
+            (3.0, 2.2090496998585438e-05),
+            (-1.0, 1.842700792949715),
+        ] {
+            assert_close(erfc(x), expected, 1.2e-7 * expected);
+        }
+        assert_close(two_sided_p_value(1.959963984540054), 0.05, 1e-7);
+    }
+
+    #[test]
+    fn mann_whitney_with_ties() {
+        // Ranks: 1 | 2 2 2 -> 3 | 3 -> 5 | 4 4 4 -> 7 | 5 -> 9 | 6 6 -> 10.5 | 7 -> 12 | 8 -> 13
+        let baseline = [1.0, 2.0, 2.0, 3.0, 4.0, 4.0];
+        let candidate = [2.0, 4.0, 5.0, 6.0, 6.0, 7.0, 8.0];
+        let test = mann_whitney(&baseline, &candidate, 0.05).unwrap();
+        // Candidate rank sum 65 - 7 * 8 / 2
+        assert_eq!(test.u, 37.0);
+        assert_close(test.z, 2.3145067832908586, 1e-12);
+        assert_close(test.p_value, 0.02063993959926729, 1e-7);
+        assert_close(test.probability_candidate_slower, 37.0 / 42.0, 1e-12);
+        assert!(test.significant);
+
+        let same = mann_whitney(&baseline, &baseline, 0.05).unwrap();
+        assert_eq!(same.z, 0.0);
+        assert!(!same.significant);
+
+        // All values tied: no variance, never significant
+        let tied = mann_whitney(&[5.0, 5.0], &[5.0, 5.0, 5.0], 0.05).unwrap();
+        assert!(!tied.significant);
+        assert!(mann_whitney(&[], &[1.0], 0.05).is_none());
+    }
+
+    #[test]
+    fn proportion_test_example() {
+        // 50 of 1000 vs 80 of 1000 failed: pooled 6.5%
+        let test = proportion_test((50, 1000), (80, 1000), 0.05).unwrap();
+        assert_close(test.z, 2.721095431170472, 1e-12);
+        assert_close(test.p_value, 0.006506597483637838, 1e-7);
+        assert!(test.significant);
+
+        assert!(!proportion_test((50, 1000), (55, 1000), 0.05).unwrap().significant);
+        // No failures on either side
+        assert!(!proportion_test((0, 1000), (0, 1000), 0.05).unwrap().significant);
+        assert!(proportion_test((0, 0), (5, 100), 0.05).is_none());
+    }
+
+    #[test]
+    fn bootstrap_intervals_cover_the_shift() {
+        let options = CompareOptions::default();
+        let mut rng = StdRng::seed_from_u64(BOOTSTRAP_SEED);
+
+        let constant = bootstrap_intervals(&[5.0; 20], &[8.0; 30], &[Statistic::Mean], &options, &mut rng).unwrap();
+        assert_eq!(constant, vec![(3.0, 3.0)]);
+
+        let baseline: Vec<f64> = (0..200).map(f64::from).collect();
+        let candidate: Vec<f64> = (50..250).map(f64::from).collect();
+        let statistics = [Statistic::Mean, Statistic::Percentile(50.0)];
+        let intervals = bootstrap_intervals(&baseline, &candidate, &statistics, &options, &mut rng).unwrap();
+        for (low, high) in intervals {
+            assert!(low > 0.0 && low < 50.0 && high > 50.0, "({}, {}) should contain 50 and exclude 0", low, high);
+        }
+
+        let none = bootstrap_intervals(&baseline, &baseline, &[Statistic::Mean], &options, &mut rng).unwrap();
+        assert!(none[0].0 < 0.0 && none[0].1 > 0.0);
+        assert!(bootstrap_intervals(&[], &candidate, &statistics, &options, &mut rng).is_none());
+    }
+
+    #[test]
+    fn metric_verdicts() {
+        let options = CompareOptions::default();
+        let verdict = |baseline, candidate, significant, higher_is_worse| {
+            metric("m", baseline, candidate, None, significant, higher_is_worse, &options).verdict
+        };
+        assert_eq!(verdict(100.0, 120.0, Some(true), true), ChangeVerdict::Regressed);
+        assert_eq!(verdict(100.0, 120.0, Some(true), false), ChangeVerdict::Improved);
+        assert_eq!(verdict(100.0, 80.0, Some(true), true), ChangeVerdict::Improved);
+        // Not significant, too small, or not tested
+        assert_eq!(verdict(100.0, 120.0, Some(false), true), ChangeVerdict::Unchanged);
+        assert_eq!(verdict(100.0, 102.0, Some(true), true), ChangeVerdict::Unchanged);
+        assert_eq!(verdict(100.0, 200.0, None, true), ChangeVerdict::Unchanged);
+        // Any significant change from a zero baseline is large
+        assert_eq!(verdict(0.0, 0.5, Some(true), true), ChangeVerdict::Regressed);
+    }
+}
@@ -0,0 +1,128 @@
+//! Request body compression and response body decoding.
+//!
+//! Request bodies are compressed once when the test starts, so every request sends the same
+//! pre-encoded bytes. Responses are decoded in `make_request` instead of by reqwest, which
+//! lets the result report both the bytes read off the wire and the decoded size.
+
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+/// Accept-Encoding sent when response decompression is enabled
+pub(crate) const ACCEPT_ENCODING: &str = "gzip, deflate, br, zstd";
+
+/// Brotli settings for request bodies (compressed once, so favor ratio over speed)
+const BROTLI_BUFFER_SIZE: usize = 4096;
+const BROTLI_QUALITY: u32 = 11;
+const BROTLI_WINDOW_BITS: u32 = 22;
+
+/// zstd level for request bodies (library default)
+const ZSTD_LEVEL: i32 = 3;
+
+/// Content-Encoding applied to the request body
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum BodyCompression {
+    /// Body sent as-is
+    #[default]
+    None,
+    Gzip,
+    Brotli,
+    Zstd,
+}
+
+impl BodyCompression {
+    /// Value of the Content-Encoding header for this compression
+    pub(crate) fn content_encoding(self) -> Option<&'static str> {
+        match self {
+            BodyCompression::None => None,
+            BodyCompression::Gzip => Some("gzip"),
+            BodyCompression::Brotli => Some("br"),
+            BodyCompression::Zstd => Some("zstd"),
+        }
+    }
+}
+
+/// Request and response compression totals for a run
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CompressionStats {
+    /// Content-Encoding of the request body, if compressed
+    pub request_encoding: Option<String>,
+    /// Request body size before compression
+    pub request_body_bytes: u64,
+    /// Request body size as sent
+    pub request_wire_bytes: u64,
+    /// Responses that carried a Content-Encoding and were decoded
+    pub compressed_responses: u32,
+    /// Response body bytes read off the wire, all responses
+    pub response_wire_bytes: u64,
+    /// Response body bytes after decoding, all responses
+    pub response_decoded_bytes: u64,
+    /// Decoded / wire bytes for compressed responses (0 when none)
+    pub response_compression_ratio: f64,
+    pub avg_decode_time_ms: f64,
+    pub max_decode_time_ms: f64,
+}
+
+/// Compresses a request body with the given encoding
+pub(crate) fn compress(body: &[u8], compression: BodyCompression) -> Result<Vec<u8>, String> {
+    match compression {
+        BodyCompression::None => Ok(body.to_vec()),
+        BodyCompression::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(body).map_err(|e| e.to_string())?;
+            encoder.finish().map_err(|e| e.to_string())
+        }
+        BodyCompression::Brotli => {
+            let mut out = Vec::new();
+            {
+                let mut writer = brotli::CompressorWriter::new(
+                    &mut out,
+                    BROTLI_BUFFER_SIZE,
+                    BROTLI_QUALITY,
+                    BROTLI_WINDOW_BITS,
+                );
+                writer.write_all(body).map_err(|e| e.to_string())?;
+            } // writer flushes the final block on drop
+            Ok(out)
+        }
+        BodyCompression::Zstd => zstd::encode_all(body, ZSTD_LEVEL).map_err(|e| e.to_string()),
+    }
+}
+
+/// Decodes a response body according to its Content-Encoding header.
+/// Codings are listed in the order they were applied, so they are undone in reverse.
+/// Returns None when the body is not encoded.
+pub(crate) fn decode(content_encoding: &str, body: &[u8]) -> Result<Option<Vec<u8>>, String> {
+    let codings: Vec<String> = content_encoding
+        .split(',')
+        .map(|coding| coding.trim().to_ascii_lowercase())
+        .filter(|coding| !coding.is_empty() && coding != "identity")
+        .collect();
+    if codings.is_empty() {
+        return Ok(None);
+    }
+
+    let mut decoded = body.to_vec();
+    for coding in codings.iter().rev() {
+        decoded = decode_one(coding, &decoded)?;
+    }
+    Ok(Some(decoded))
+}
+
+fn decode_one(coding: &str, body: &[u8]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    let result = match coding {
+        "gzip" | "x-gzip" => flate2::read::MultiGzDecoder::new(body).read_to_end(&mut out),
+        "deflate" => {
+            // "deflate" is meant to be zlib-wrapped, but some servers send raw deflate
+            flate2::read::ZlibDecoder::new(body).read_to_end(&mut out).or_else(|_| {
+                out.clear();
+                flate2::read::DeflateDecoder::new(body).read_to_end(&mut out)
+            })
+        }
+        "br" => brotli::Decompressor::new(body, BROTLI_BUFFER_SIZE).read_to_end(&mut out),
+        "zstd" => zstd::stream::read::Decoder::new(body).and_then(|mut decoder| decoder.read_to_end(&mut out)),
+        other => return Err(format!("unsupported content encoding '{}'", other)),
+    };
+    result.map_err(|e| format!("invalid {} data: {}", coding, e))?;
+    Ok(out)
+}
@@ -1,24 +1,44 @@
+mod auth;
+mod autostop;
+mod batch;
+mod capacity;
+mod compare;
+mod compression;
+mod history;
+mod pacing;
+mod progress;
+mod runs;
+mod signing;
+mod slo;
+mod windows;
+
+use auth::{AuthConfig, AuthStats, Authenticator};
+use autostop::{AutoStopConfig, AutoStopMonitor, StopReason};
+use compression::{BodyCompression, CompressionStats};
+use pacing::{ThinkTime, MAX_PACING_SECS};
+use progress::ProgressAggregator;
+use runs::{RunEvent, RunHandle, RunId, RunStarted, RunStatus};
+use signing::{RequestSigner, SigningConfig};
+use slo::{SloThreshold, SloVerdict};
+use windows::{WindowAggregator, WindowStats};
 use bytes::Bytes;
-use futures::stream::{self, StreamExt};
+use futures::future::Either;
+use futures::stream::{FuturesUnordered, StreamExt};
 use rand::seq::SliceRandom;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::error::Error as StdError;
-use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 use thiserror::Error;
-use tokio::sync::mpsc;
-
-// Global cancellation flag - no Arc needed for static lifetime
-static CANCEL_FLAG: AtomicBool = AtomicBool::new(false);
-/// Generation counter to scope cancellation per-test run (prevents cross-test interference)
-static TEST_GENERATION: AtomicU64 = AtomicU64::new(0);
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::{mpsc, Semaphore};
 
-// Progress throttling - stores last emission time as millis since UNIX_EPOCH
-static LAST_PROGRESS_MS: AtomicU64 = AtomicU64::new(0);
+// Progress throttling - interval between progress events of a run
 const PROGRESS_THROTTLE_MS: u64 = 100; // ~10 updates per second (frontend further throttles to 5Hz)
 
 /// Poll interval for cancellation checks in async operations
@@ -33,10 +53,26 @@ const CONCURRENCY_SAMPLE_TARGET: usize = 200;
 const CONCURRENCY_MIN_SAMPLES: usize = 20;
 const TIMELINE_SAMPLE_TARGET: usize = 500;
 const ERROR_LOGS_MAX: usize = 1000;
+/// Distinct error groups kept; errors with a new fingerprint beyond this are only counted
+const ERROR_GROUPS_MAX: usize = 200;
+/// Example entries kept per error group
+const ERROR_GROUP_EXAMPLES: usize = 3;
+/// Allowed window lengths of the percentile and error rate series, in seconds
+const STATS_WINDOW_SECS_RANGE: std::ops::RangeInclusive<f64> = 0.1..=3600.0;
+/// Lowest non-zero per-worker rate limit, in requests per second
+pub(crate) const MIN_RATE_LIMIT: f64 = 0.001;
+/// Bytes per megabyte for bandwidth figures (decimal, as in MB/s)
+const BYTES_PER_MB: f64 = 1_000_000.0;
 
 /// Default capacity for status code HashMap (typical tests have 1-5 unique codes)
 const STATUS_MAP_CAPACITY: usize = 8;
 
+/// HTTP2-Settings payload sent with `Upgrade: h2c` (base64url SETTINGS frame payload,
+/// same as curl: max concurrent streams 100, initial window 32MB, push disabled)
+const H2C_UPGRADE_SETTINGS: &str = "AAMAAABkAAQCAAAAAAIAAAAA";
+/// Maximum size of the h2c upgrade response head we are willing to read
+const H2C_UPGRADE_MAX_RESPONSE: usize = 16 * 1024;
+
 // User-Agent pool for randomization
 static USER_AGENTS: &[&str] = &[
     // Chrome on Windows
@@ -87,6 +123,8 @@ pub enum LoadTestError {
     InvalidConfig(String),
     #[error("Internal error: {0}")]
     Internal(String),
+    #[error("History database error: {0}")]
+    Database(#[from] rusqlite::Error),
 }
 
 impl Serialize for LoadTestError {
@@ -110,6 +148,82 @@ pub enum HttpMethod {
     OPTIONS,
 }
 
+/// How HTTP/2 is negotiated when `use_http2` is enabled
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum Http2Mode {
+    /// Speak HTTP/2 immediately without negotiation (h2c prior knowledge for http URLs)
+    #[default]
+    PriorKnowledge,
+    /// Cleartext HTTP/1.1 `Upgrade: h2c` handshake, falling back to HTTP/1.1 if declined.
+    /// The handshake is made once, directly to the target, before the test; when accepted,
+    /// every connection of the test then speaks h2c with prior knowledge. Not usable with a proxy.
+    Upgrade,
+    /// Negotiate via TLS ALPN (https only), falling back to HTTP/1.1 if not offered
+    Alpn,
+}
+
+/// Which IP address families to connect to when resolving the target host
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum IpPreference {
+    /// Use addresses in the order the system resolver returns them
+    #[default]
+    Any,
+    /// Try IPv4 addresses before IPv6
+    PreferV4,
+    /// Try IPv6 addresses before IPv4
+    PreferV6,
+    /// Only connect over IPv4
+    V4Only,
+    /// Only connect over IPv6
+    V6Only,
+}
+
+impl IpPreference {
+    /// Whether connections to or from `address` are allowed
+    fn allows(self, address: &IpAddr) -> bool {
+        match self {
+            IpPreference::V4Only => address.is_ipv4(),
+            IpPreference::V6Only => address.is_ipv6(),
+            _ => true,
+        }
+    }
+}
+
+/// How cookies set by the target are stored and sent back
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum CookieMode {
+    /// No cookie store - Set-Cookie responses are ignored
+    #[default]
+    Disabled,
+    /// One cookie jar shared by all workers
+    Shared,
+    /// One cookie jar per worker (virtual user), isolating sessions
+    PerVirtualUser,
+}
+
+/// Capture of response headers and body prefixes, for failed and sampled successful requests
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResponseCaptureConfig {
+    /// Maximum body bytes kept per response (the rest is discarded)
+    #[serde(default = "default_capture_body_bytes")]
+    pub max_body_bytes: u32,
+    /// Fraction of successful responses to capture as well (0.0 - 1.0)
+    #[serde(default)]
+    pub success_sample_rate: f64,
+    /// Total bytes (headers + bodies) kept across the run, including copies in error logs and
+    /// error group examples; further captures are dropped
+    #[serde(default = "default_capture_memory_budget")]
+    pub memory_budget_bytes: u64,
+}
+
+fn default_capture_body_bytes() -> u32 {
+    2048
+}
+
+fn default_capture_memory_budget() -> u64 {
+    16 * 1024 * 1024
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct CustomHeader {
     pub key: String,
@@ -129,7 +243,7 @@ pub struct FormFieldConfig {
     pub file_name: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LoadTestConfig {
     pub url: String,
     pub num_requests: u32,
@@ -142,7 +256,7 @@ pub struct LoadTestConfig {
     /// Timeout for each request in seconds. 0 means infinite.
     #[serde(default = "default_timeout")]
     pub timeout_secs: f64,
-    /// Rate limit in queries per second per worker. 0 means no limit.
+    /// Rate limit in queries per second per worker (at least 0.001). 0 means no limit.
     #[serde(default)]
     pub rate_limit: f64,
     #[serde(default)]
@@ -157,9 +271,22 @@ pub struct LoadTestConfig {
     /// Number of worker threads to use. 0 means use all available CPU cores.
     #[serde(default)]
     pub worker_threads: u32,
-    /// HTTP proxy address in format "host:port" or "http://host:port". Empty string means no proxy.
+    /// Proxy address in format "host:port" or a URL with http, https, socks5 or socks5h scheme
+    /// (credentials may be embedded as "user:pass@"). Empty string means no proxy.
     #[serde(default)]
     pub proxy_url: String,
+    /// Additional proxies to rotate requests across, round-robin with one client per proxy.
+    #[serde(default)]
+    pub proxy_urls: Vec<String>,
+    /// Proxy basic auth username, used for proxies without credentials in their URL.
+    #[serde(default)]
+    pub proxy_username: Option<String>,
+    /// Proxy basic auth password.
+    #[serde(default)]
+    pub proxy_password: Option<String>,
+    /// Hosts that bypass the proxy (NO_PROXY syntax: domains, IP addresses, CIDR ranges).
+    #[serde(default)]
+    pub no_proxy: Vec<String>,
     /// Request body payload (optional, used for POST, PUT, PATCH methods). Empty string means no body.
     #[serde(default)]
     pub body: Option<String>,
@@ -169,18 +296,106 @@ pub struct LoadTestConfig {
     /// Form fields for multipart/form-data (optional, alternative to body). If set, body is ignored.
     #[serde(default)]
     pub form_fields: Option<Vec<FormFieldConfig>>,
+    /// Path to a Unix domain socket to send requests through instead of TCP (Unix only).
+    /// The URL host is still used for the Host header. Set automatically for `http+unix://` URLs.
+    #[serde(default)]
+    pub unix_socket_path: Option<String>,
+    /// How HTTP/2 is negotiated when `use_http2` is enabled.
+    #[serde(default)]
+    pub http2_mode: Http2Mode,
+    /// Number of HTTP/2 connections to spread requests across. 0 means a single shared client.
+    #[serde(default)]
+    pub http2_connections: u32,
+    /// Maximum concurrent streams per HTTP/2 connection. 0 means no limit besides concurrency.
+    #[serde(default)]
+    pub http2_max_streams_per_connection: u32,
+    /// Local source IPs to bind outgoing connections to, one client per address with
+    /// requests spread round-robin. Empty means the OS picks the source address.
+    #[serde(default)]
+    pub local_addresses: Vec<IpAddr>,
+    /// IPv4/IPv6 preference when resolving the target host.
+    #[serde(default)]
+    pub ip_preference: IpPreference,
+    /// Authentication applied to every request (Basic, Bearer, Digest or OAuth2).
+    #[serde(default)]
+    pub auth: Option<AuthConfig>,
+    /// Request signing (HMAC or AWS SigV4), applied after authentication.
+    #[serde(default)]
+    pub signing: Option<SigningConfig>,
+    /// Cookie handling: disabled, one shared jar, or one jar per worker (virtual user).
+    #[serde(default)]
+    pub cookie_mode: CookieMode,
+    /// Cookies every jar starts with, in Set-Cookie syntax (e.g. "session=abc; Path=/").
+    /// Scoped to the target host unless a Domain attribute is given.
+    #[serde(default)]
+    pub seed_cookies: Vec<String>,
+    /// Compress the request body (sets Content-Encoding). The body is compressed once at start.
+    #[serde(default)]
+    pub request_compression: BodyCompression,
+    /// Send Accept-Encoding and decode compressed responses, reporting wire and decoded sizes.
+    #[serde(default)]
+    pub decompress_responses: bool,
+    /// Keep response headers and the start of the body for failed (and sampled) requests.
+    #[serde(default)]
+    pub response_capture: Option<ResponseCaptureConfig>,
+    /// Safety thresholds that abort the test early (error rate, p95, connection errors).
+    #[serde(default)]
+    pub auto_stop: Option<AutoStopConfig>,
+    /// Pass/fail thresholds on the final stats (e.g. p99 < 300ms), reported as a verdict. All
+    /// apply to the whole run; one with an `endpoint` other than `url` rejects the config.
+    #[serde(default)]
+    pub slo_thresholds: Vec<SloThreshold>,
+    /// Requests started in the first `warmup_secs` are sent but kept out of the aggregate
+    /// stats (connection setup, TLS handshakes, server warm-up). 0 disables.
+    #[serde(default)]
+    pub warmup_secs: f64,
+    /// The first `warmup_requests` results are kept out of the aggregate stats. 0 disables.
+    #[serde(default)]
+    pub warmup_requests: u32,
+    /// Delay each virtual user waits after a response before its next request
+    #[serde(default)]
+    pub think_time: ThinkTime,
+    /// Start each iteration of a virtual user every `pacing_secs`, regardless of response
+    /// time (think time included). 0 disables; at most 3600.
+    #[serde(default)]
+    pub pacing_secs: f64,
+    /// Window length of the percentile and error rate series (`window_stats`), 0.1 to 3600 seconds
+    #[serde(default = "default_stats_window_secs")]
+    pub stats_window_secs: f64,
+    /// Save the run (config, stats and time series) to the local history database. Off unless
+    /// requested; the saved config has credentials, the body and form field values redacted.
+    #[serde(default)]
+    pub save_to_history: bool,
+    /// Also save every request result, so `results` can be loaded from history later
+    #[serde(default)]
+    pub save_raw_results: bool,
+    /// Tags stored with the saved run
+    #[serde(default)]
+    pub history_tags: Vec<String>,
+    /// Leave `results` out of the returned stats (load them from history when saved with
+    /// `save_raw_results`), so large runs don't have to be sent to the frontend
+    #[serde(default)]
+    pub omit_results: bool,
+    /// Opaque value echoed in the `load-test-started` event, so a caller can tell its run
+    /// apart from others started at the same time
+    #[serde(default)]
+    pub client_token: Option<String>,
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_stats_window_secs() -> f64 {
+    1.0
+}
+
 fn default_timeout() -> f64 {
     20.0
 }
 
 /// Error type classification for failed requests
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Default)]
 pub enum ErrorType {
     /// No error - request succeeded
     #[default]
@@ -195,6 +410,10 @@ pub enum ErrorType {
     Response,
     /// Redirect error (too many redirects, redirect loop)
     Redirect,
+    /// No local address/ephemeral port available for a new connection (EADDRNOTAVAIL)
+    PortExhaustion,
+    /// Credentials could not be obtained (OAuth2 token fetch) - request not sent to the target
+    Auth,
     /// Other/unknown error
     Other,
 }
@@ -209,6 +428,31 @@ pub struct RequestResult {
     #[serde(default)]
     pub error_type: ErrorType,
     pub timestamp_ms: f64, // Time since test start when request completed
+    /// Index into the test's proxy list when rotating across proxies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_index: Option<u32>,
+    /// Response body bytes as received (before decoding)
+    #[serde(default)]
+    pub body_wire_bytes: u64,
+    /// Response body bytes after decoding (equal to wire bytes when not encoded)
+    #[serde(default)]
+    pub body_decoded_bytes: u64,
+    /// Time spent decoding a compressed response body (included in duration)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decode_time_ms: Option<f64>,
+    /// Request size as issued: request line, headers and body (HTTP/1.1 framing;
+    /// approximate under HTTP/2, where headers are compressed). 0 if never sent.
+    #[serde(default)]
+    pub request_bytes: u64,
+    /// Response size: status line, headers and body as received. 0 without a response.
+    #[serde(default)]
+    pub response_bytes: u64,
+    /// Response headers and body prefix, when response capture selected this request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captured_response: Option<CapturedResponse>,
+    /// Part of the warm-up phase: shown in time series, excluded from aggregates
+    #[serde(default)]
+    pub warmup: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -229,21 +473,86 @@ pub struct LatencyPercentiles {
     pub p99: f64,
 }
 
+/// Response size distribution in bytes (status line, headers and body)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SizePercentiles {
+    pub avg: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct StatusCodeCount {
     pub code: u16,
     pub count: u32,
 }
 
+/// Results for a single proxy (credentials redacted)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProxyStats {
+    pub proxy: String,
+    pub total_requests: u32,
+    pub successful_requests: u32,
+    pub failed_requests: u32,
+    pub avg_response_time_ms: f64,
+    pub p50_response_time_ms: f64,
+    pub p95_response_time_ms: f64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProgressUpdate {
+    /// Run the update belongs to
+    pub run_id: RunId,
     pub completed: u32,
     pub total: u32,
     pub successful: u32,
     pub failed: u32,
+    /// Cumulative rate: completed requests over active (unpaused) time
     pub current_rps: f64,
     pub elapsed_secs: f64,
     pub latest_response_time_ms: f64,
+    /// Length of the sliding window the window_* fields cover (shorter early in the test)
+    #[serde(default)]
+    pub window_secs: f64,
+    /// Requests per second completed within the sliding window
+    #[serde(default)]
+    pub window_rps: f64,
+    #[serde(default)]
+    pub window_p50_ms: f64,
+    #[serde(default)]
+    pub window_p95_ms: f64,
+    #[serde(default)]
+    pub window_p99_ms: f64,
+    #[serde(default)]
+    pub window_error_rate_percent: f64,
+    /// Requests sent and awaiting a response
+    #[serde(default)]
+    pub in_flight: u32,
+    /// Responses per status code so far (0 = no response)
+    #[serde(default)]
+    pub status_codes: Vec<StatusCodeCount>,
+}
+
+/// Change to a running test's settings, marked on the time series
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Annotation {
+    /// Seconds since test start
+    pub time_secs: f64,
+    pub message: String,
+    /// Worker count after the change
+    pub concurrency: u32,
+    /// Rate limit per worker after the change (0 = unlimited)
+    pub rate_limit: f64,
+}
+
+/// Span of wall time during which the test was paused, in seconds since test start
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PauseInterval {
+    pub start_secs: f64,
+    pub end_secs: f64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -251,6 +560,18 @@ pub struct ThroughputDataPoint {
     pub time_secs: f64,
     pub requests_completed: u32,
     pub rps: f64,
+    /// Whether the test was paused during (part of) this bucket
+    #[serde(default)]
+    pub paused: bool,
+    /// Request bytes sent in this bucket, in MB/s
+    #[serde(default)]
+    pub sent_mb_per_sec: f64,
+    /// Response bytes received in this bucket, in MB/s
+    #[serde(default)]
+    pub received_mb_per_sec: f64,
+    /// Whether the bucket holds warm-up results
+    #[serde(default)]
+    pub warmup: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -258,6 +579,47 @@ pub struct LatencyDataPoint {
     pub request_num: u32,
     pub latency_ms: f64,
     pub timestamp_ms: f64,
+    #[serde(default)]
+    pub warmup: bool,
+}
+
+/// Response headers and the first bytes of the (decoded) body
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CapturedResponse {
+    pub headers: Vec<CustomHeader>,
+    /// Body prefix, lossily converted to UTF-8
+    pub body: String,
+    /// Full body size in bytes
+    pub body_size: u64,
+    /// Whether the body was cut at the capture limit
+    pub truncated: bool,
+}
+
+/// Failed requests sharing the same error type, status and normalized message
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ErrorGroup {
+    /// Stable key: error type, status and normalized message
+    pub fingerprint: String,
+    pub error_type: ErrorType,
+    pub status: u16,
+    /// Message with URLs replaced by `<url>` and hex IDs and numbers by `#`
+    pub message: String,
+    pub count: u32,
+    pub first_seen_ms: f64,
+    pub last_seen_ms: f64,
+    /// First few occurrences, with their original messages
+    pub examples: Vec<ErrorLogEntry>,
+}
+
+/// Totals for response capture
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CaptureStats {
+    pub captured: u32,
+    /// Responses selected for capture, or their copies in error logs and error group examples,
+    /// dropped because the memory budget was spent
+    pub dropped_over_budget: u32,
+    /// Bytes held by captures, including their copies
+    pub bytes_used: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -267,6 +629,9 @@ pub struct ErrorLogEntry {
     pub error: String,
     pub error_type: ErrorType,
     pub duration_ms: f64,
+    /// Captured response headers and body, when response capture is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<CapturedResponse>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -283,6 +648,9 @@ pub struct RequestTimelinePoint {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LoadTestStats {
+    /// Run these stats belong to
+    #[serde(default)]
+    pub run_id: RunId,
     pub total_requests: u32,
     pub successful_requests: u32,
     pub failed_requests: u32,
@@ -300,6 +668,91 @@ pub struct LoadTestStats {
     pub error_logs: Vec<ErrorLogEntry>,
     pub concurrency_over_time: Vec<ConcurrencyDataPoint>,
     pub request_timeline: Vec<RequestTimelinePoint>,
+    /// Number of connections actually opened by the client during the test
+    #[serde(default)]
+    pub connections_opened: u32,
+    /// HTTP version of the first response received (e.g. "HTTP/2.0"), None if no response
+    #[serde(default)]
+    pub http_version: Option<String>,
+    /// Per-proxy breakdown, one entry per configured proxy (empty without a proxy)
+    #[serde(default)]
+    pub proxy_stats: Vec<ProxyStats>,
+    /// Token fetches and Digest challenges, present when authentication is configured
+    #[serde(default)]
+    pub auth_stats: Option<AuthStats>,
+    /// Request compression and response decoding totals, present when either is enabled
+    #[serde(default)]
+    pub compression: Option<CompressionStats>,
+    /// Total request bytes sent (request lines, headers and bodies)
+    #[serde(default)]
+    pub total_bytes_sent: u64,
+    /// Total response bytes received (status lines, headers and bodies)
+    #[serde(default)]
+    pub total_bytes_received: u64,
+    /// Average upload bandwidth over the test, in MB/s
+    #[serde(default)]
+    pub sent_mb_per_sec: f64,
+    /// Average download bandwidth over the test, in MB/s
+    #[serde(default)]
+    pub received_mb_per_sec: f64,
+    /// Response size distribution, over requests that received a response
+    #[serde(default)]
+    pub response_size_percentiles: SizePercentiles,
+    /// Response capture totals, present when capture is enabled
+    #[serde(default)]
+    pub capture_stats: Option<CaptureStats>,
+    /// Failures grouped by fingerprint, most frequent first
+    #[serde(default)]
+    pub error_groups: Vec<ErrorGroup>,
+    /// Failures not grouped because `ERROR_GROUPS_MAX` distinct fingerprints were reached
+    #[serde(default)]
+    pub ungrouped_errors: u32,
+    /// Set when an auto-stop threshold ended the test early (stats are partial)
+    #[serde(default)]
+    pub stop_reason: Option<StopReason>,
+    /// True when the user cancelled the test; stats cover the completed requests only
+    #[serde(default)]
+    pub cancelled: bool,
+    /// Wall time spent paused, excluded from requests per second and bandwidth
+    #[serde(default)]
+    pub paused_time_secs: f64,
+    /// When the test was paused, in seconds since test start
+    #[serde(default)]
+    pub pause_intervals: Vec<PauseInterval>,
+    /// Concurrency and rate changes made while the test ran
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+    /// SLO pass/fail verdict, present when thresholds are configured
+    #[serde(default)]
+    pub slo_verdict: Option<SloVerdict>,
+    /// Warm-up results, sent but excluded from counts, latencies, rates and error groups
+    #[serde(default)]
+    pub warmup_requests: u32,
+    /// Start of the measured phase in seconds since test start (0 without warm-up);
+    /// rates are computed from here
+    #[serde(default)]
+    pub measurement_start_secs: f64,
+    /// p50/p90/p99/max latency and error rate per window of completion time
+    #[serde(default)]
+    pub window_stats: Vec<WindowStats>,
+    /// Id of the run in the history database, when it was saved
+    #[serde(default)]
+    pub history_id: Option<i64>,
+    /// Why saving to history failed; the run itself completed
+    #[serde(default)]
+    pub history_error: Option<String>,
+}
+
+impl LoadTestStats {
+    /// Failed requests as a percentage of completed requests (0 when none completed)
+    pub(crate) fn error_rate_percent(&self) -> f64 {
+        let completed = self.successful_requests + self.failed_requests;
+        if completed == 0 {
+            0.0
+        } else {
+            self.failed_requests as f64 * 100.0 / completed as f64
+        }
+    }
 }
 
 /// Calculates the given percentile from a sorted slice of response times.
@@ -354,6 +807,7 @@ fn calculate_throughput_over_time(
     results: &[RequestResult],
     total_time_secs: f64,
     sorted_by_timestamp: &[usize],
+    pause_intervals: &[PauseInterval],
 ) -> Vec<ThroughputDataPoint> {
     if results.is_empty() || total_time_secs <= 0.0 {
         return vec![];
@@ -371,31 +825,48 @@ fn calculate_throughput_over_time(
         let bucket_start = i as f64 * bucket_duration;
         let bucket_end = (i + 1) as f64 * bucket_duration;
         let mut requests_in_bucket: u32 = 0;
+        let mut bytes_sent: u64 = 0;
+        let mut bytes_received: u64 = 0;
+        let mut warmup = false;
         
         // Count requests in this bucket using the pre-sorted order
         while result_index < sorted_by_timestamp.len() {
-            let timestamp_secs = results[sorted_by_timestamp[result_index]].timestamp_ms / 1000.0;
+            let result = &results[sorted_by_timestamp[result_index]];
+            let timestamp_secs = result.timestamp_ms / 1000.0;
             if timestamp_secs >= bucket_end {
                 break;
             }
             if timestamp_secs >= bucket_start {
                 requests_in_bucket += 1;
+                bytes_sent += result.request_bytes;
+                bytes_received += result.response_bytes;
+                warmup |= result.warmup;
             }
             cumulative += 1;
             result_index += 1;
         }
         
-        // Calculate RPS for this bucket
-        let rps = if bucket_duration > 0.0 {
-            requests_in_bucket as f64 / bucket_duration
+        // Calculate RPS and bandwidth for this bucket
+        let (rps, sent_mb_per_sec, received_mb_per_sec) = if bucket_duration > 0.0 {
+            (
+                requests_in_bucket as f64 / bucket_duration,
+                bytes_sent as f64 / BYTES_PER_MB / bucket_duration,
+                bytes_received as f64 / BYTES_PER_MB / bucket_duration,
+            )
         } else {
-            0.0
+            (0.0, 0.0, 0.0)
         };
         
         throughput_data.push(ThroughputDataPoint {
             time_secs: bucket_end,
             requests_completed: cumulative,
             rps,
+            sent_mb_per_sec,
+            received_mb_per_sec,
+            warmup,
+            paused: pause_intervals
+                .iter()
+                .any(|pause| pause.start_secs < bucket_end && pause.end_secs > bucket_start),
         });
     }
     
@@ -426,6 +897,7 @@ fn calculate_latency_over_time(
                 request_num: i as u32 + 1,
                 latency_ms: r.duration_ms,
                 timestamp_ms: r.timestamp_ms,
+                warmup: r.warmup,
             }
         })
         .collect()
@@ -537,22 +1009,181 @@ fn calculate_request_timeline(results: &[RequestResult]) -> Vec<RequestTimelineP
         .collect()
 }
 
-/// Check if enough time has passed to emit a progress update (throttling)
-#[inline]
-fn should_emit_progress() -> bool {
-    let now_ms = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.as_millis() as u64)
-        .unwrap_or(0);
+/// Calculates success and latency per proxy from the results tagged with a proxy index.
+#[must_use]
+fn calculate_proxy_stats(results: &[RequestResult], proxies: &[String]) -> Vec<ProxyStats> {
+    let mut times_by_proxy: Vec<Vec<f64>> = vec![Vec::new(); proxies.len()];
+    let mut successful_by_proxy = vec![0u32; proxies.len()];
     
-    let last = LAST_PROGRESS_MS.load(Ordering::Relaxed);
-    if now_ms.saturating_sub(last) >= PROGRESS_THROTTLE_MS {
-        // Try to update - if another thread beat us, that's fine
-        let _ = LAST_PROGRESS_MS.compare_exchange(last, now_ms, Ordering::Relaxed, Ordering::Relaxed);
-        true
-    } else {
-        false
+    for result in results.iter().filter(|r| !r.warmup) {
+        let Some(index) = result.proxy_index.map(|i| i as usize).filter(|&i| i < proxies.len()) else {
+            continue;
+        };
+        times_by_proxy[index].push(result.duration_ms);
+        if result.success {
+            successful_by_proxy[index] += 1;
+        }
+    }
+    
+    proxies
+        .iter()
+        .zip(times_by_proxy)
+        .zip(successful_by_proxy)
+        .map(|((proxy, mut times), successful_requests)| {
+            times.sort_by(|a, b| a.total_cmp(b));
+            let total_requests = times.len() as u32;
+            let avg_response_time_ms = if times.is_empty() {
+                0.0
+            } else {
+                times.iter().sum::<f64>() / times.len() as f64
+            };
+            ProxyStats {
+                proxy: redact_proxy_url(proxy),
+                total_requests,
+                successful_requests,
+                failed_requests: total_requests - successful_requests,
+                avg_response_time_ms,
+                p50_response_time_ms: calculate_percentile(&times, 50.0),
+                p95_response_time_ms: calculate_percentile(&times, 95.0),
+            }
+        })
+        .collect()
+}
+
+/// Normalizes an error message for grouping: URLs (which carry cache busters and
+/// per-request query strings) become `<url>`, hex IDs (UUIDs, hashes) become `#` and digit
+/// runs (ports, durations, IDs) become `#`
+fn normalize_error_message(message: &str) -> String {
+    const OPENERS: &[char] = &['(', '[', '<', '"', '\''];
+    const CLOSERS: &[char] = &[')', ']', '>', '"', '\'', ',', ';', ':', '.'];
+    
+    let mut normalized = String::with_capacity(message.len());
+    for (i, token) in message.split(' ').enumerate() {
+        if i > 0 {
+            normalized.push(' ');
+        }
+        let core = token.trim_start_matches(OPENERS).trim_end_matches(CLOSERS);
+        let replacement = if core.contains("://") {
+            Some("<url>")
+        } else if is_hex_id(core) {
+            Some("#")
+        } else {
+            None
+        };
+        if let Some(replacement) = replacement {
+            let prefix_len = token.len() - token.trim_start_matches(OPENERS).len();
+            let suffix_start = prefix_len + core.len();
+            normalized.push_str(&token[..prefix_len]);
+            normalized.push_str(replacement);
+            normalized.push_str(&token[suffix_start..]);
+            continue;
+        }
+        let mut in_digits = false;
+        for c in token.chars() {
+            if c.is_ascii_digit() {
+                if !in_digits {
+                    normalized.push('#');
+                }
+                in_digits = true;
+            } else {
+                normalized.push(c);
+                in_digits = false;
+            }
+        }
+    }
+    normalized
+}
+
+/// Whether a token looks like a generated ID: at least 8 hex digits and dashes, with a digit
+fn is_hex_id(token: &str) -> bool {
+    token.len() >= 8
+        && token.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+        && token.chars().any(|c| c.is_ascii_digit())
+}
+
+/// Groups failed requests by fingerprint (error type + status + normalized message),
+/// walking results in completion order so first/last seen and examples are chronological.
+/// Returns groups sorted by count and the number of failures that did not fit.
+#[must_use]
+fn calculate_error_groups(results: &[RequestResult], sorted_by_timestamp: &[usize]) -> (Vec<ErrorGroup>, u32) {
+    let mut groups: Vec<ErrorGroup> = Vec::new();
+    let mut index_by_key: HashMap<(ErrorType, u16, String), usize> = HashMap::new();
+    let mut ungrouped = 0u32;
+    
+    for result in sorted_by_timestamp.iter().map(|&i| &results[i]).filter(|r| !r.success) {
+        let error = result.error.clone().unwrap_or_else(|| format!("HTTP {}", result.status));
+        let key = (result.error_type.clone(), result.status, normalize_error_message(&error));
+        
+        let index = match index_by_key.get(&key) {
+            Some(&index) => index,
+            None if groups.len() < ERROR_GROUPS_MAX => {
+                let (error_type, status, message) = &key;
+                groups.push(ErrorGroup {
+                    fingerprint: format!("{:?}|{}|{}", error_type, status, message),
+                    error_type: error_type.clone(),
+                    status: *status,
+                    message: message.clone(),
+                    count: 0,
+                    first_seen_ms: result.timestamp_ms,
+                    last_seen_ms: result.timestamp_ms,
+                    examples: Vec::with_capacity(ERROR_GROUP_EXAMPLES),
+                });
+                index_by_key.insert(key, groups.len() - 1);
+                groups.len() - 1
+            }
+            None => {
+                ungrouped += 1;
+                continue;
+            }
+        };
+        
+        let group = &mut groups[index];
+        group.count += 1;
+        group.last_seen_ms = result.timestamp_ms;
+        if group.examples.len() < ERROR_GROUP_EXAMPLES {
+            group.examples.push(ErrorLogEntry {
+                timestamp_ms: result.timestamp_ms,
+                status: result.status,
+                error,
+                error_type: result.error_type.clone(),
+                duration_ms: result.duration_ms,
+                response: result.captured_response.clone(),
+            });
+        }
+    }
+    
+    groups.sort_by_key(|group| std::cmp::Reverse(group.count));
+    (groups, ungrouped)
+}
+
+/// Totals response wire/decoded bytes and decoding time across results.
+/// Request body sizes are filled in by the caller.
+#[must_use]
+fn calculate_compression_stats(results: &[RequestResult]) -> CompressionStats {
+    let mut stats = CompressionStats::default();
+    let mut compressed_wire_bytes = 0u64;
+    let mut compressed_decoded_bytes = 0u64;
+    let mut total_decode_time_ms = 0.0;
+    
+    for result in results.iter().filter(|r| !r.warmup) {
+        stats.response_wire_bytes += result.body_wire_bytes;
+        stats.response_decoded_bytes += result.body_decoded_bytes;
+        if let Some(decode_time_ms) = result.decode_time_ms {
+            stats.compressed_responses += 1;
+            compressed_wire_bytes += result.body_wire_bytes;
+            compressed_decoded_bytes += result.body_decoded_bytes;
+            total_decode_time_ms += decode_time_ms;
+            stats.max_decode_time_ms = stats.max_decode_time_ms.max(decode_time_ms);
+        }
     }
+    
+    if stats.compressed_responses > 0 {
+        stats.avg_decode_time_ms = total_decode_time_ms / stats.compressed_responses as f64;
+    }
+    if compressed_wire_bytes > 0 {
+        stats.response_compression_ratio = compressed_decoded_bytes as f64 / compressed_wire_bytes as f64;
+    }
+    stats
 }
 
 /// Cached file content for multipart form-data
@@ -576,8 +1207,6 @@ struct TestConfig {
     url: Arc<str>,
     method: HttpMethod,
     headers: Arc<[CustomHeader]>,
-    /// Minimum interval between requests per worker (for rate limiting)
-    rate_limit_interval: Option<Duration>,
     randomize_user_agent: bool,
     randomize_headers: bool,
     add_cache_buster: bool,
@@ -590,6 +1219,16 @@ struct TestConfig {
     disable_keep_alive: bool,
     /// Form fields for multipart/form-data (optional, takes precedence over body)
     form_fields: Option<Arc<[FormField]>>,
+    /// Content-Encoding of the pre-compressed body
+    content_encoding: Option<&'static str>,
+    /// Send Accept-Encoding and decode compressed responses
+    decompress_responses: bool,
+    /// Response capture settings (None = disabled)
+    response_capture: Option<ResponseCaptureConfig>,
+    /// Think time between iterations of a virtual user
+    think_time: ThinkTime,
+    /// Interval between iteration starts of a virtual user (None = no pacing)
+    pacing: Option<Duration>,
 }
 
 /// Shared mutable counters for progress tracking
@@ -599,6 +1238,16 @@ struct TestCounters {
     failed: AtomicU32,
     /// Connection errors specifically (for adaptive pooling)
     connection_errors: AtomicU32,
+    /// Connections established by the connector (shared by all clients)
+    connections_opened: Arc<AtomicU32>,
+    /// HTTP version of the first response received
+    http_version: OnceLock<reqwest::Version>,
+    /// Bytes held by captured responses (bounded by the capture memory budget)
+    capture_bytes_used: AtomicU64,
+    captured_responses: AtomicU32,
+    captures_dropped: AtomicU32,
+    /// Requests started and not yet finished
+    in_flight: AtomicU32,
 }
 
 impl TestCounters {
@@ -608,10 +1257,119 @@ impl TestCounters {
             successful: AtomicU32::new(0),
             failed: AtomicU32::new(0),
             connection_errors: AtomicU32::new(0),
+            connections_opened: Arc::new(AtomicU32::new(0)),
+            http_version: OnceLock::new(),
+            capture_bytes_used: AtomicU64::new(0),
+            captured_responses: AtomicU32::new(0),
+            captures_dropped: AtomicU32::new(0),
+            in_flight: AtomicU32::new(0),
+        }
+    }
+}
+
+/// Counts a request as in flight until dropped, however the request ends
+struct InFlight<'a>(&'a AtomicU32);
+
+impl<'a> InFlight<'a> {
+    fn new(counter: &'a AtomicU32) -> Self {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Self(counter)
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+tokio::task_local! {
+    /// Cookie jar of the virtual user (worker) whose request is currently being polled
+    static SESSION_JAR: Arc<reqwest::cookie::Jar>;
+}
+
+/// Cookie store routing to the current worker's jar, giving each virtual user its own
+/// session while all workers share the same clients and connection pools
+struct VirtualUserCookies;
+
+impl reqwest::cookie::CookieStore for VirtualUserCookies {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &reqwest::header::HeaderValue>, url: &url::Url) {
+        let _ = SESSION_JAR.try_with(|jar| jar.set_cookies(cookie_headers, url));
+    }
+    
+    fn cookies(&self, url: &url::Url) -> Option<reqwest::header::HeaderValue> {
+        SESSION_JAR.try_with(|jar| jar.cookies(url)).ok().flatten()
+    }
+}
+
+/// Creates a cookie jar pre-filled with the configured seed cookies
+fn new_cookie_jar(seed_cookies: &[String], url: &url::Url) -> Arc<reqwest::cookie::Jar> {
+    let jar = reqwest::cookie::Jar::default();
+    for cookie in seed_cookies {
+        jar.add_cookie_str(cookie, url);
+    }
+    Arc::new(jar)
+}
+
+/// A client in the pool used by a test run. With `http2_connections` set, each slot
+/// owns exactly one HTTP/2 connection and limits the streams multiplexed over it.
+struct ClientSlot {
+    client: reqwest::Client,
+    /// Caps in-flight requests (streams) on this client, if configured
+    stream_limit: Option<Arc<Semaphore>>,
+    /// Index of the proxy this client goes through, if any
+    proxy_index: Option<u32>,
+}
+
+/// Tower layer counting connections established by the client's connector
+#[derive(Clone)]
+struct ConnectionCountLayer {
+    opened: Arc<AtomicU32>,
+}
+
+impl<S> tower::Layer<S> for ConnectionCountLayer {
+    type Service = ConnectionCountService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConnectionCountService {
+            inner,
+            opened: Arc::clone(&self.opened),
         }
     }
 }
 
+#[derive(Clone)]
+struct ConnectionCountService<S> {
+    inner: S,
+    opened: Arc<AtomicU32>,
+}
+
+impl<S, R> tower::Service<R> for ConnectionCountService<S>
+where
+    S: tower::Service<R>,
+    S::Response: Send + 'static,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = futures::future::BoxFuture<'static, Result<S::Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut std::task::Context<'_>) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        let opened = Arc::clone(&self.opened);
+        let connecting = self.inner.call(req);
+        Box::pin(async move {
+            let conn = connecting.await?;
+            opened.fetch_add(1, Ordering::Relaxed);
+            Ok(conn)
+        })
+    }
+}
+
 /// Threshold for connection errors before switching to fresh connections
 /// If more than 5% of completed requests have connection errors, force fresh connections
 const CONNECTION_ERROR_THRESHOLD_PERCENT: u32 = 5;
@@ -620,23 +1378,20 @@ const MIN_REQUESTS_FOR_ADAPTIVE: u32 = 50;
 
 /// Context for making requests - groups related parameters
 struct RequestContext {
-    client: reqwest::Client,
+    /// Clients to spread requests across (round-robin)
+    clients: Arc<[ClientSlot]>,
+    next_client: AtomicUsize,
+    /// Authentication applied to each built request
+    auth: Option<Authenticator>,
+    /// Signs each built request after authentication
+    signer: Option<Box<dyn RequestSigner>>,
     config: Arc<TestConfig>,
     counters: Arc<TestCounters>,
+    /// Registry entry of this run: cancellation, pause, progress throttle, live settings
+    run: Arc<RunHandle>,
     app_handle: AppHandle,
     total: u32,
     start_time: Instant,
-    cancel_flag: &'static AtomicBool,
-    /// Test generation when this context was created (for scoped cancellation)
-    test_generation: u64,
-}
-
-/// Check if the current test should be cancelled.
-/// Cancellation is scoped to the test generation to prevent cross-test interference.
-#[inline]
-fn is_cancelled(cancel_flag: &AtomicBool, expected_generation: u64) -> bool {
-    // Only consider cancelled if the flag is set AND we're still on the same generation
-    cancel_flag.load(Ordering::SeqCst) && TEST_GENERATION.load(Ordering::SeqCst) == expected_generation
 }
 
 /// Macro to check cancellation and return early if cancelled.
@@ -644,56 +1399,209 @@ fn is_cancelled(cancel_flag: &AtomicBool, expected_generation: u64) -> bool {
 macro_rules! check_cancelled {
     // Return None (for Option-returning functions)
     ($ctx:expr) => {
-        if is_cancelled($ctx.cancel_flag, $ctx.test_generation) {
+        if $ctx.run.is_cancelled() {
             return None;
         }
     };
     // Return unit with optional cleanup (for async closures)
     ($ctx:expr, return) => {
-        if is_cancelled($ctx.cancel_flag, $ctx.test_generation) {
+        if $ctx.run.is_cancelled() {
             return;
         }
     };
 }
 
-async fn make_request(ctx: &RequestContext, result_tx: &mpsc::UnboundedSender<RequestResult>) -> Option<()> {
-    // Check if cancelled before starting (scoped to this test's generation)
-    check_cancelled!(ctx);
-
-    let request_start = Instant::now();
+/// Builds the result for a request that failed without a response, classifying the error
+fn error_result(ctx: &RequestContext, slot: &ClientSlot, e: &reqwest::Error, duration: Duration) -> RequestResult {
+    let timestamp = ctx.start_time.elapsed();
     
-    // Build URL and request with randomization in a non-async block
-    // This ensures the RNG doesn't live across await points
-    let config = &ctx.config;
-    let request = {
-        let mut rng = rand::thread_rng();
-        
-        // Build URL with optional cache buster
-        let url = if config.add_cache_buster {
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map(|d| d.as_nanos())
-                .unwrap_or(0);
-            let random_suffix: u32 = rng.gen();
-            let cache_buster = format!("_cb={}_{}", timestamp, random_suffix);
-            
-            if config.url.contains('?') {
-                format!("{}&{}", config.url, cache_buster)
-            } else {
-                format!("{}?{}", config.url, cache_buster)
-            }
+    // Classify the error type with resource exhaustion detection
+    let err_str = e.to_string().to_lowercase();
+    
+    // Port exhaustion is not tracked for adaptive pooling: forcing fresh
+    // connections would only burn through local ports faster
+    let port_exhausted = is_address_unavailable(e);
+    
+    // Track connection errors for adaptive pooling behavior
+    // Both is_connect() and the stale connection case (is_connect + is_request) are tracked
+    if e.is_connect() && !port_exhausted {
+        ctx.counters.connection_errors.fetch_add(1, Ordering::Relaxed);
+    }
+    
+    let error_type = if e.is_timeout() {
+        ErrorType::Timeout
+    } else if port_exhausted {
+        ErrorType::PortExhaustion
+    } else if e.is_connect() {
+        ErrorType::Connection
+    } else if e.is_request() {
+        ErrorType::Request
+    } else if e.is_redirect() {
+        ErrorType::Redirect
+    } else {
+        ErrorType::Other
+    };
+    
+    // Build full error string including source chain for pattern matching
+    let full_err_str = {
+        let mut parts = vec![err_str.clone()];
+        let mut source = e.source();
+        while let Some(src) = source {
+            parts.push(src.to_string().to_lowercase());
+            source = src.source();
+        }
+        parts.join(" ")
+    };
+    
+    // Create error message
+    let error_msg = match error_type {
+        ErrorType::Timeout => format!("Timeout after {}ms", duration.as_millis()),
+        ErrorType::Connection => {
+            // Simpler error messages for common connection errors
+            // Check full error chain for patterns
+            if full_err_str.contains("dns") || full_err_str.contains("resolve") {
+                format!("DNS resolution failed: {}", e)
+            } else if full_err_str.contains("refused") {
+                "Connection refused by server".to_string()
+            } else if full_err_str.contains("reset") {
+                "Connection reset by server".to_string()
+            } else if full_err_str.contains("too many open files") || full_err_str.contains("emfile") {
+                "Too many open connections (reduce concurrency)".to_string()
+            } else if full_err_str.contains("closed") || full_err_str.contains("broken pipe") {
+                // Different message based on whether connection pooling is enabled
+                if ctx.config.disable_keep_alive {
+                    // No pool = server is closing connections (likely overloaded)
+                    "Connection closed by server (server may be overloaded)".to_string()
+                } else {
+                    // Pool enabled = likely a stale connection from the pool
+                    "Connection closed (stale connection from pool)".to_string()
+                }
+            } else {
+                format!("Connection failed: {}", e)
+            }
+        }
+        ErrorType::PortExhaustion => {
+            "Local ports exhausted (add local addresses or reduce concurrency)".to_string()
+        }
+        ErrorType::Request => format!("Request error: {}", e),
+        ErrorType::Redirect => format!("Redirect error: {}", e),
+        _ => e.to_string(),
+    };
+    
+    RequestResult {
+        status: 0,
+        duration_ms: duration.as_secs_f64() * 1000.0,
+        success: false,
+        error: Some(error_msg),
+        error_type,
+        timestamp_ms: timestamp.as_secs_f64() * 1000.0,
+        proxy_index: slot.proxy_index,
+        body_wire_bytes: 0,
+        body_decoded_bytes: 0,
+        decode_time_ms: None,
+        request_bytes: 0,
+        response_bytes: 0,
+        captured_response: None,
+        warmup: false,
+    }
+}
+
+/// Builds the result for a request that failed before being sent (credentials, signing)
+fn local_failure_result(
+    ctx: &RequestContext,
+    slot: &ClientSlot,
+    error_type: ErrorType,
+    error: String,
+    duration: Duration,
+) -> RequestResult {
+    RequestResult {
+        status: 0,
+        duration_ms: duration.as_secs_f64() * 1000.0,
+        success: false,
+        error: Some(error),
+        error_type,
+        timestamp_ms: ctx.start_time.elapsed().as_secs_f64() * 1000.0,
+        proxy_index: slot.proxy_index,
+        body_wire_bytes: 0,
+        body_decoded_bytes: 0,
+        decode_time_ms: None,
+        request_bytes: 0,
+        response_bytes: 0,
+        captured_response: None,
+        warmup: false,
+    }
+}
+
+/// Updates counters and sends the result to the collector, which emits progress
+fn record_result(ctx: &RequestContext, result_tx: &mpsc::UnboundedSender<RequestResult>, result: RequestResult) {
+    // Update counters (Relaxed ordering is sufficient for counters - no synchronization needed)
+    let counters = &ctx.counters;
+    counters.completed.fetch_add(1, Ordering::Relaxed);
+    if result.success {
+        counters.successful.fetch_add(1, Ordering::Relaxed);
+    } else {
+        counters.failed.fetch_add(1, Ordering::Relaxed);
+    }
+    
+    // Send result through channel (non-blocking)
+    let _ = result_tx.send(result);
+}
+
+/// Sends a request, letting the authenticator answer a Digest challenge when configured
+async fn execute_request(
+    ctx: &RequestContext,
+    client: &reqwest::Client,
+    request: reqwest::Request,
+) -> reqwest::Result<reqwest::Response> {
+    match &ctx.auth {
+        Some(auth) => auth.execute(client, request).await,
+        None => client.execute(request).await,
+    }
+}
+
+async fn make_request(ctx: &RequestContext, result_tx: &mpsc::UnboundedSender<RequestResult>) -> Option<()> {
+    // Check if cancelled before starting
+    check_cancelled!(ctx);
+    let _in_flight = InFlight::new(&ctx.counters.in_flight);
+
+    let request_start = Instant::now();
+    
+    // Pick the next client round-robin (one slot per HTTP/2 connection when multiplexing)
+    let slot = &ctx.clients[ctx.next_client.fetch_add(1, Ordering::Relaxed) % ctx.clients.len()];
+    let client = &slot.client;
+    
+    // Build URL and request with randomization in a non-async block
+    // This ensures the RNG doesn't live across await points
+    let config = &ctx.config;
+    let request = {
+        let mut rng = rand::thread_rng();
+        
+        // Build URL with optional cache buster
+        let url = if config.add_cache_buster {
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0);
+            let random_suffix: u32 = rng.gen();
+            let cache_buster = format!("_cb={}_{}", timestamp, random_suffix);
+            
+            if config.url.contains('?') {
+                format!("{}&{}", config.url, cache_buster)
+            } else {
+                format!("{}?{}", config.url, cache_buster)
+            }
         } else {
             config.url.to_string()
         };
         
         let mut request = match &config.method {
-            HttpMethod::GET => ctx.client.get(&url),
-            HttpMethod::POST => ctx.client.post(&url),
-            HttpMethod::PUT => ctx.client.put(&url),
-            HttpMethod::DELETE => ctx.client.delete(&url),
-            HttpMethod::PATCH => ctx.client.patch(&url),
-            HttpMethod::HEAD => ctx.client.head(&url),
-            HttpMethod::OPTIONS => ctx.client.request(reqwest::Method::OPTIONS, &url),
+            HttpMethod::GET => client.get(&url),
+            HttpMethod::POST => client.post(&url),
+            HttpMethod::PUT => client.put(&url),
+            HttpMethod::DELETE => client.delete(&url),
+            HttpMethod::PATCH => client.patch(&url),
+            HttpMethod::HEAD => client.head(&url),
+            HttpMethod::OPTIONS => client.request(reqwest::Method::OPTIONS, &url),
         };
         
         // Check if we should force fresh connections (adaptive behavior)
@@ -755,15 +1663,22 @@ async fn make_request(ctx: &RequestContext, result_tx: &mpsc::UnboundedSender<Re
         // Add custom headers (these override randomized ones if same key)
         // Check if Content-Type is already set by user
         let mut has_content_type = false;
+        let mut has_accept_encoding = false;
         for header in config.headers.iter() {
             if !header.key.is_empty() {
                 if header.key.eq_ignore_ascii_case("Content-Type") {
                     has_content_type = true;
+                } else if header.key.eq_ignore_ascii_case("Accept-Encoding") {
+                    has_accept_encoding = true;
                 }
                 request = request.header(&header.key, &header.value);
             }
         }
         
+        if config.decompress_responses && !has_accept_encoding {
+            request = request.header("Accept-Encoding", compression::ACCEPT_ENCODING);
+        }
+        
         // Add body or multipart form for POST, PUT, PATCH methods
         if let Some(form_fields) = &config.form_fields {
             // Build multipart form from cached form fields
@@ -790,6 +1705,9 @@ async fn make_request(ctx: &RequestContext, result_tx: &mpsc::UnboundedSender<Re
                         request = request.header("Content-Type", content_type);
                     }
                 }
+                if let Some(content_encoding) = config.content_encoding {
+                    request = request.header("Content-Encoding", content_encoding);
+                }
                 request = request.body(body.clone());
             }
         }
@@ -797,16 +1715,55 @@ async fn make_request(ctx: &RequestContext, result_tx: &mpsc::UnboundedSender<Re
         request
     }; // rng is dropped here, before any await
     
-    // Check if cancelled before sending
-    check_cancelled!(ctx);
+    // Finalize the request so authentication sees the exact method and URL being sent
+    let mut request = match request.build() {
+        Ok(request) => request,
+        Err(e) => {
+            record_result(ctx, result_tx, error_result(ctx, slot, &e, request_start.elapsed()));
+            return Some(());
+        }
+    };
+    
+    if let Some(auth) = &ctx.auth {
+        if let Err(error) = auth.authorize(&mut request).await {
+            // Credentials unavailable - the request never reaches the target
+            let result = local_failure_result(ctx, slot, ErrorType::Auth, error, request_start.elapsed());
+            record_result(ctx, result_tx, result);
+            return Some(());
+        }
+    }
+    
+    // Sign last so the signature covers the final URL, headers and body
+    if let Some(signer) = &ctx.signer {
+        if let Err(error) = signer.sign(&mut request) {
+            let message = format!("Request signing failed: {}", error);
+            let result = local_failure_result(ctx, slot, ErrorType::Request, message, request_start.elapsed());
+            record_result(ctx, result_tx, result);
+            return Some(());
+        }
+    }
+    
+    let request_bytes = request_size(&request);
+    
+    // Wait for a free stream on this connection; time spent queued counts toward latency,
+    // just like a real HTTP/2 client blocked by the server's max concurrent streams
+    let _stream_permit = match &slot.stream_limit {
+        Some(limit) => Some(Arc::clone(limit).acquire_owned().await.ok()?),
+        None => None,
+    };
     
-    // Create a cancellation check future that polls periodically (scoped to test generation)
-    let cancel_flag_ref = ctx.cancel_flag;
-    let test_generation = ctx.test_generation;
+    // Check if cancelled before sending (a draining cancel also stops requests that were
+    // still queued for a stream and never reached the server)
+    if ctx.run.is_stopping() {
+        return None;
+    }
+    
+    // Create a cancellation check future that polls periodically
+    let run = &ctx.run;
     let cancel_check = async move {
         loop {
             tokio::time::sleep(Duration::from_millis(CANCEL_POLL_MS)).await;
-            if is_cancelled(cancel_flag_ref, test_generation) {
+            if run.is_cancelled() {
                 return;
             }
         }
@@ -814,25 +1771,63 @@ async fn make_request(ctx: &RequestContext, result_tx: &mpsc::UnboundedSender<Re
     
     // Race between the request and cancellation
     let result = tokio::select! {
-        response = request.send() => {
+        response = execute_request(ctx, client, request) => {
             match response {
                 Ok(response) => {
                     // Check cancellation before reading body
                     check_cancelled!(ctx);
+                    let _ = ctx.counters.http_version.set(response.version());
                     let status = response.status().as_u16();
-                    let success = response.status().is_success();
+                    let mut success = response.status().is_success();
+                    let response_head_bytes = response_head_size(&response);
+                    // Headers are only copied when a capture may be taken
+                    let captured_headers = config.response_capture.is_some().then(|| response.headers().clone());
+                    let content_encoding = response
+                        .headers()
+                        .get(reqwest::header::CONTENT_ENCODING)
+                        .and_then(|value| value.to_str().ok())
+                        .map(str::to_owned);
                     // Consume body to ensure connection can be reused
-                    let _ = response.bytes().await;
+                    let body = response.bytes().await.unwrap_or_default();
+                    let body_wire_bytes = body.len() as u64;
+                    let mut body_decoded_bytes = body_wire_bytes;
+                    let mut decode_time_ms = None;
+                    let mut decoded_body = None;
+                    let mut decode_error = None;
+                    
+                    // Decode compressed bodies ourselves so both sizes are known; decoding
+                    // time is part of the request duration, like in a real client
+                    if let (true, Some(content_encoding)) = (config.decompress_responses, &content_encoding) {
+                        let decode_start = Instant::now();
+                        match compression::decode(content_encoding, &body) {
+                            Ok(Some(decoded)) => {
+                                body_decoded_bytes = decoded.len() as u64;
+                                decode_time_ms = Some(decode_start.elapsed().as_secs_f64() * 1000.0);
+                                decoded_body = Some(decoded);
+                            }
+                            Ok(None) => {}
+                            Err(e) => decode_error = Some(format!("Response decoding failed: {}", e)),
+                        }
+                    }
+                    
                     let duration = request_start.elapsed();
                     let timestamp = ctx.start_time.elapsed();
                     
                     // Check if this is a server error (4xx/5xx) even though request "succeeded"
                     let (error, error_type) = if !success {
                         (Some(format!("HTTP {}", status)), ErrorType::Response)
+                    } else if let Some(decode_error) = decode_error {
+                        success = false;
+                        (Some(decode_error), ErrorType::Other)
                     } else {
                         (None, ErrorType::None)
                     };
                     
+                    let captured_response = captured_headers.and_then(|headers| {
+                        let body = decoded_body.as_deref().unwrap_or(&body);
+                        capture_response(ctx, &headers, body, success)
+                    });
+                    
                     RequestResult {
                         status,
                         duration_ms: duration.as_secs_f64() * 1000.0,
@@ -840,85 +1835,20 @@ async fn make_request(ctx: &RequestContext, result_tx: &mpsc::UnboundedSender<Re
                         error,
                         error_type,
                         timestamp_ms: timestamp.as_secs_f64() * 1000.0,
+                        proxy_index: slot.proxy_index,
+                        body_wire_bytes,
+                        body_decoded_bytes,
+                        decode_time_ms,
+                        request_bytes,
+                        response_bytes: response_head_bytes + body_wire_bytes,
+                        captured_response,
+                        warmup: false,
                     }
                 }
-                Err(e) => {
-                    let duration = request_start.elapsed();
-                    let timestamp = ctx.start_time.elapsed();
-                    
-                    // Classify the error type with resource exhaustion detection
-                    let err_str = e.to_string().to_lowercase();
-                    
-                    // Track connection errors for adaptive pooling behavior
-                    // Both is_connect() and the stale connection case (is_connect + is_request) are tracked
-                    if e.is_connect() {
-                        ctx.counters.connection_errors.fetch_add(1, Ordering::Relaxed);
-                    }
-                    
-                    let error_type = if e.is_timeout() {
-                        ErrorType::Timeout
-                    } else if e.is_connect() {
-                        ErrorType::Connection
-                    } else if e.is_request() {
-                        ErrorType::Request
-                    } else if e.is_redirect() {
-                        ErrorType::Redirect
-                    } else {
-                        ErrorType::Other
-                    };
-                    
-                    // Build full error string including source chain for pattern matching
-                    let full_err_str = {
-                        let mut parts = vec![err_str.clone()];
-                        let mut source = e.source();
-                        while let Some(src) = source {
-                            parts.push(src.to_string().to_lowercase());
-                            source = src.source();
-                        }
-                        parts.join(" ")
-                    };
-                    
-                    // Create error message
-                    let error_msg = match error_type {
-                        ErrorType::Timeout => format!("Timeout after {}ms", duration.as_millis()),
-                        ErrorType::Connection => {
-                            // Simpler error messages for common connection errors
-                            // Check full error chain for patterns
-                            if full_err_str.contains("dns") || full_err_str.contains("resolve") {
-                                format!("DNS resolution failed: {}", e)
-                            } else if full_err_str.contains("refused") {
-                                "Connection refused by server".to_string()
-                            } else if full_err_str.contains("reset") {
-                                "Connection reset by server".to_string()
-                            } else if full_err_str.contains("too many open files") || full_err_str.contains("emfile") {
-                                "Too many open connections (reduce concurrency)".to_string()
-                            } else if full_err_str.contains("closed") || full_err_str.contains("broken pipe") {
-                                // Different message based on whether connection pooling is enabled
-                                if ctx.config.disable_keep_alive {
-                                    // No pool = server is closing connections (likely overloaded)
-                                    "Connection closed by server (server may be overloaded)".to_string()
-                                } else {
-                                    // Pool enabled = likely a stale connection from the pool
-                                    "Connection closed (stale connection from pool)".to_string()
-                                }
-                            } else {
-                                format!("Connection failed: {}", e)
-                            }
-                        }
-                        ErrorType::Request => format!("Request error: {}", e),
-                        ErrorType::Redirect => format!("Redirect error: {}", e),
-                        _ => e.to_string(),
-                    };
-                    
-                    RequestResult {
-                        status: 0,
-                        duration_ms: duration.as_secs_f64() * 1000.0,
-                        success: false,
-                        error: Some(error_msg),
-                        error_type,
-                        timestamp_ms: timestamp.as_secs_f64() * 1000.0,
-                    }
-                }
+                Err(e) => RequestResult {
+                    request_bytes,
+                    ..error_result(ctx, slot, &e, request_start.elapsed())
+                },
             }
         }
         _ = cancel_check => {
@@ -930,51 +1860,359 @@ async fn make_request(ctx: &RequestContext, result_tx: &mpsc::UnboundedSender<Re
     // Check if cancelled after request
     check_cancelled!(ctx);
 
-    // Update counters (Relaxed ordering is sufficient for counters - no synchronization needed)
+    record_result(ctx, result_tx, result);
+    
+    Some(())
+}
+
+/// Captures headers and a body prefix for failed responses and a sample of successful ones,
+/// within the run's capture memory budget
+fn capture_response(
+    ctx: &RequestContext,
+    headers: &reqwest::header::HeaderMap,
+    body: &[u8],
+    success: bool,
+) -> Option<CapturedResponse> {
+    let capture = ctx.config.response_capture.as_ref()?;
+    if success && !(capture.success_sample_rate > 0.0 && rand::thread_rng().gen_bool(capture.success_sample_rate)) {
+        return None;
+    }
+    
+    let kept = &body[..body.len().min(capture.max_body_bytes as usize)];
+    let size = (kept.len() as u64) + header_block_size(headers);
+    
+    // Reserve budget up front so concurrent captures cannot overshoot it
     let counters = &ctx.counters;
-    let new_completed = counters.completed.fetch_add(1, Ordering::Relaxed) + 1;
-    if result.success {
-        counters.successful.fetch_add(1, Ordering::Relaxed);
+    if counters.capture_bytes_used.fetch_add(size, Ordering::Relaxed) + size > capture.memory_budget_bytes {
+        counters.capture_bytes_used.fetch_sub(size, Ordering::Relaxed);
+        counters.captures_dropped.fetch_add(1, Ordering::Relaxed);
+        return None;
+    }
+    counters.captured_responses.fetch_add(1, Ordering::Relaxed);
+    
+    Some(CapturedResponse {
+        headers: headers
+            .iter()
+            .map(|(name, value)| CustomHeader {
+                key: name.to_string(),
+                value: String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            })
+            .collect(),
+        body: String::from_utf8_lossy(kept).into_owned(),
+        body_size: body.len() as u64,
+        truncated: kept.len() < body.len(),
+    })
+}
+
+/// Charges the captures copied into error logs and error group examples against the capture
+/// memory budget, dropping the copies that no longer fit
+fn charge_capture_copies(stats: &mut LoadTestStats, budget_bytes: u64, capture_stats: &mut CaptureStats) {
+    let LoadTestStats { error_logs, error_groups, .. } = stats;
+    let entries = error_logs.iter_mut().chain(error_groups.iter_mut().flat_map(|group| group.examples.iter_mut()));
+    for entry in entries {
+        let Some(response) = &entry.response else { continue };
+        let size = captured_size(response);
+        if capture_stats.bytes_used + size > budget_bytes {
+            entry.response = None;
+            capture_stats.dropped_over_budget += 1;
+        } else {
+            capture_stats.bytes_used += size;
+        }
+    }
+}
+
+/// Memory held by a capture, counted like `header_block_size` plus the kept body
+fn captured_size(capture: &CapturedResponse) -> u64 {
+    let fields: usize = capture.headers.iter().map(|header| header.key.len() + header.value.len() + 4).sum();
+    (capture.body.len() + fields + 2) as u64
+}
+
+/// Size of a header block in HTTP/1.1 framing: "name: value\r\n" per header plus the blank line
+fn header_block_size(headers: &reqwest::header::HeaderMap) -> u64 {
+    let fields: usize = headers.iter().map(|(name, value)| name.as_str().len() + value.len() + 4).sum();
+    (fields + 2) as u64
+}
+
+/// Size of a request in HTTP/1.1 framing: request line, Host header if not set explicitly,
+/// headers and body. Headers added by the client at send time (e.g. Accept) are not counted.
+fn request_size(request: &reqwest::Request) -> u64 {
+    let url = request.url();
+    let target_len = url.path().len() + url.query().map_or(0, |query| query.len() + 1);
+    // "METHOD target HTTP/1.1\r\n"
+    let request_line = request.method().as_str().len() + target_len + 12;
+    let host = if request.headers().contains_key(reqwest::header::HOST) {
+        0
     } else {
-        counters.failed.fetch_add(1, Ordering::Relaxed);
+        let host_len = url.host_str().map_or(0, str::len) + url.port().map_or(0, |port| port.to_string().len() + 1);
+        host_len + 8 // "Host: " + CRLF
+    };
+    // Multipart bodies are streamed; their size is in the Content-Length header
+    let body = request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .map(|bytes| bytes.len() as u64)
+        .or_else(|| {
+            request
+                .headers()
+                .get(reqwest::header::CONTENT_LENGTH)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok())
+        })
+        .unwrap_or(0);
+    (request_line + host) as u64 + header_block_size(request.headers()) + body
+}
+
+/// Size of a response status line and headers in HTTP/1.1 framing
+fn response_head_size(response: &reqwest::Response) -> u64 {
+    // "HTTP/1.1 200 Reason\r\n"
+    let reason_len = response.status().canonical_reason().map_or(0, str::len);
+    (reason_len + 15) as u64 + header_block_size(response.headers())
+}
+
+/// Worker (virtual user) loop: claims request indices until all are issued, honoring
+/// cancellation, pause, think time, pacing and the live concurrency and rate limit.
+/// Returns the worker's slot.
+async fn run_worker(
+    ctx: Arc<RequestContext>,
+    result_tx: mpsc::UnboundedSender<RequestResult>,
+    next_request: Arc<AtomicU32>,
+    worker_id: usize,
+) -> usize {
+    // Start of this worker's previous request, for rate limiting and pacing
+    let mut last_start: Option<tokio::time::Instant> = None;
+    
+    // Retire when concurrency was lowered below this worker's slot
+    while worker_id < ctx.run.control.concurrency.load(Ordering::Relaxed) as usize
+        && next_request.fetch_add(1, Ordering::Relaxed) < ctx.total
+    {
+        // Check cancellation before starting
+        if ctx.run.is_stopping() {
+            break;
+        }
+        
+        // Between iterations: think time after the previous response, and request starts
+        // spaced at least the rate limit or pacing interval apart (whichever is longer), so
+        // the worker keeps the configured rate as long as responses keep up
+        let think_until = last_start
+            .and(ctx.config.think_time.sample())
+            .map(|think_time| tokio::time::Instant::now() + think_time);
+        let interval = ctx.run.control.rate_limit_interval().max(ctx.config.pacing);
+        let next_start = last_start.zip(interval).map(|(last_start, interval)| last_start + interval);
+        if let Some(deadline) = think_until.max(next_start) {
+            // Simple cancellation check during the delay - no complex select! loop needed
+            tokio::select! {
+                biased;
+                _ = tokio::time::sleep_until(deadline) => {}
+                _ = async {
+                    while !ctx.run.is_stopping() {
+                        tokio::time::sleep(Duration::from_millis(CANCEL_POLL_MS)).await;
+                    }
+                } => {
+                    break;
+                }
+            }
+            // A draining cancel may have arrived during the delay
+            if ctx.run.is_stopping() {
+                break;
+            }
+        }
+        
+        // Hold dispatch while paused (in-flight requests complete normally)
+        while ctx.run.is_paused() {
+            if ctx.run.is_stopping() {
+                return worker_id;
+            }
+            tokio::time::sleep(Duration::from_millis(CANCEL_POLL_MS)).await;
+        }
+        
+        last_start = Some(tokio::time::Instant::now());
+        let _ = make_request(&ctx, &result_tx).await;
+    }
+    worker_id
+}
+
+/// Checks the error source chain for EADDRNOTAVAIL, raised when no local address or
+/// ephemeral port is available for a new connection
+fn is_address_unavailable(err: &(dyn StdError + 'static)) -> bool {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if let Some(io_err) = err.downcast_ref::<std::io::Error>() {
+            if io_err.kind() == std::io::ErrorKind::AddrNotAvailable {
+                return true;
+            }
+        }
+        source = err.source();
+    }
+    false
+}
+
+/// Active runs addressed by a command: the given run, or every active run when None
+fn target_runs(run_id: Option<RunId>) -> Result<Vec<Arc<RunHandle>>, LoadTestError> {
+    match run_id {
+        Some(run_id) => runs::get(run_id)
+            .map(|run| vec![run])
+            .ok_or_else(|| LoadTestError::InvalidConfig(format!("No active run with id {}", run_id))),
+        None => Ok(runs::all()),
+    }
+}
+
+/// Pauses a run (or all runs): no new requests are dispatched until resumed, in-flight
+/// requests complete. Paused time is excluded from rates and reported as pause intervals.
+#[tauri::command]
+async fn pause_load_test(app_handle: AppHandle, run_id: Option<RunId>) -> Result<(), LoadTestError> {
+    for run in target_runs(run_id)? {
+        if run.pause() {
+            let _ = app_handle.emit("load-test-paused", RunEvent { run_id: run.id, payload: () });
+        }
+    }
+    Ok(())
+}
+
+/// Resumes a paused run (or all paused runs)
+#[tauri::command]
+async fn resume_load_test(app_handle: AppHandle, run_id: Option<RunId>) -> Result<(), LoadTestError> {
+    for run in target_runs(run_id)? {
+        if run.resume() {
+            let _ = app_handle.emit("load-test-resumed", RunEvent { run_id: run.id, payload: () });
+        }
+    }
+    Ok(())
+}
+
+/// Changes the worker count (at most the run's request count) and/or per-worker rate limit
+/// (requests per second, 0 = unlimited) of a run (or all runs). Each change is recorded as an
+/// annotation on the time series.
+#[tauri::command]
+async fn update_running_test(
+    app_handle: AppHandle,
+    run_id: Option<RunId>,
+    concurrency: Option<u32>,
+    rate_limit: Option<f64>,
+) -> Result<(), LoadTestError> {
+    if concurrency == Some(0) {
+        return Err(LoadTestError::InvalidConfig("concurrency must be greater than 0".into()));
+    }
+    if rate_limit.is_some_and(|rate| !is_valid_rate_limit(rate)) {
+        return Err(LoadTestError::InvalidConfig(format!(
+            "rate_limit must be 0 (unlimited) or at least {} req/s",
+            MIN_RATE_LIMIT
+        )));
     }
     
-    let duration_ms = result.duration_ms;
+    let targets = target_runs(run_id)?;
+    if targets.is_empty() {
+        return Err(LoadTestError::InvalidConfig("No test is running".into()));
+    }
     
-    // Send result through channel (non-blocking)
-    let _ = result_tx.send(result);
+    if concurrency.is_none() && rate_limit.is_none() {
+        return Ok(());
+    }
     
-    // Emit progress update with throttling
-    if should_emit_progress() || new_completed == ctx.total {
-        let elapsed = ctx.start_time.elapsed().as_secs_f64();
-        let current_rps = if elapsed > 0.0 { new_completed as f64 / elapsed } else { 0.0 };
+    for run in targets {
+        let control = &run.control;
+        let mut changes = Vec::new();
+        if let Some(concurrency) = concurrency {
+            // More workers than requests would only allocate idle workers
+            changes.push(format!("concurrency {}", run.set_concurrency(concurrency)));
+        }
+        if let Some(rate_limit) = rate_limit {
+            control.set_rate_limit(rate_limit);
+            changes.push(if rate_limit > 0.0 {
+                format!("rate limit {} req/s per worker", rate_limit)
+            } else {
+                "rate limit off".to_string()
+            });
+        }
         
-        let progress = ProgressUpdate {
-            completed: new_completed,
-            total: ctx.total,
-            successful: counters.successful.load(Ordering::Relaxed),
-            failed: counters.failed.load(Ordering::Relaxed),
-            current_rps,
-            elapsed_secs: elapsed,
-            latest_response_time_ms: duration_ms,
+        let annotation = Annotation {
+            time_secs: run.start_time().elapsed().as_secs_f64(),
+            message: format!("Set {}", changes.join(", ")),
+            concurrency: control.concurrency.load(Ordering::Relaxed),
+            rate_limit: control.rate_limit(),
         };
-        
-        let _ = ctx.app_handle.emit("load-test-progress", progress);
+        let _ = app_handle.emit("load-test-updated", RunEvent { run_id: run.id, payload: annotation.clone() });
+        if let Ok(mut annotations) = control.annotations.lock() {
+            annotations.push(annotation);
+        }
     }
-    
-    Some(())
+    Ok(())
 }
 
+/// Cancels every active run, and any batch or capacity search waiting between runs. With
+/// `drain_in_flight`, only dispatch stops and requests already sent complete; otherwise
+/// in-flight requests are abandoned. Either way `run_load_test` returns stats for the
+/// completed requests with `cancelled: true`.
 #[tauri::command]
-async fn cancel_load_test(app_handle: AppHandle) -> Result<(), LoadTestError> {
-    CANCEL_FLAG.store(true, Ordering::SeqCst);
-    // Emit cancellation event so frontend can react immediately
-    let _ = app_handle.emit("load-test-cancelled", ());
+async fn cancel_load_test(app_handle: AppHandle, drain_in_flight: Option<bool>) -> Result<(), LoadTestError> {
+    for run in runs::cancel_all(drain_in_flight.unwrap_or(false)) {
+        // Emit cancellation event so frontend can react immediately
+        let _ = app_handle.emit("load-test-cancelled", RunEvent { run_id: run.id, payload: () });
+    }
     Ok(())
 }
 
+/// Cancels a single run, like `cancel_load_test`
+#[tauri::command]
+async fn cancel_run(app_handle: AppHandle, run_id: RunId, drain_in_flight: Option<bool>) -> Result<(), LoadTestError> {
+    let run = runs::get(run_id)
+        .ok_or_else(|| LoadTestError::InvalidConfig(format!("No active run with id {}", run_id)))?;
+    run.cancel(drain_in_flight.unwrap_or(false));
+    let _ = app_handle.emit("load-test-cancelled", RunEvent { run_id, payload: () });
+    Ok(())
+}
+
+/// Lists the active runs, oldest first
+#[tauri::command]
+async fn list_runs() -> Vec<RunStatus> {
+    runs::all().iter().map(|run| run.status()).collect()
+}
+
+/// Returns the status of an active run
 #[tauri::command]
-async fn run_load_test(app_handle: AppHandle, config: LoadTestConfig) -> Result<LoadTestStats, LoadTestError> {
+async fn get_run_status(run_id: RunId) -> Result<RunStatus, LoadTestError> {
+    runs::get(run_id)
+        .map(|run| run.status())
+        .ok_or_else(|| LoadTestError::InvalidConfig(format!("No active run with id {}", run_id)))
+}
+
+/// Checks an IP-literal target against the IP preference and the local source addresses,
+/// which can only connect to targets of their own family
+fn validate_target_address(
+    target: IpAddr,
+    ip_preference: IpPreference,
+    local_addresses: &[IpAddr],
+) -> Result<(), LoadTestError> {
+    if !ip_preference.allows(&target) {
+        return Err(LoadTestError::InvalidConfig(
+            format!("Target address {} conflicts with IP preference {:?}", target, ip_preference)
+        ));
+    }
+    if let Some(address) = local_addresses.iter().find(|address| address.is_ipv4() != target.is_ipv4()) {
+        return Err(LoadTestError::InvalidConfig(
+            format!("Local address {} cannot connect to target address {}", address, target)
+        ));
+    }
+    Ok(())
+}
+
+/// Workers a run uses: `concurrency`, defaulting to `num_requests` when it is 0 or greater
+pub(crate) fn effective_concurrency(concurrency: u32, num_requests: u32) -> u32 {
+    if concurrency == 0 || concurrency > num_requests {
+        num_requests
+    } else {
+        concurrency
+    }
+}
+
+/// Whether a per-worker rate limit is 0 (unlimited) or a finite rate of at least `MIN_RATE_LIMIT`
+pub(crate) fn is_valid_rate_limit(rate_limit: f64) -> bool {
+    rate_limit == 0.0 || (rate_limit.is_finite() && rate_limit >= MIN_RATE_LIMIT)
+}
+
+/// Runs a load test and returns its stats. SLO thresholds are all evaluated against the
+/// run's single URL; a threshold whose `endpoint` names another URL fails validation.
+#[tauri::command]
+async fn run_load_test(app_handle: AppHandle, mut config: LoadTestConfig) -> Result<LoadTestStats, LoadTestError> {
     // Validate configuration
     if config.num_requests == 0 {
         return Err(LoadTestError::InvalidConfig("num_requests must be greater than 0".into()));
@@ -982,22 +2220,153 @@ async fn run_load_test(app_handle: AppHandle, config: LoadTestConfig) -> Result<
     if config.url.is_empty() {
         return Err(LoadTestError::InvalidConfig("URL cannot be empty".into()));
     }
+    if !is_valid_rate_limit(config.rate_limit) {
+        return Err(LoadTestError::InvalidConfig(format!(
+            "rate_limit must be 0 (unlimited) or at least {} req/s",
+            MIN_RATE_LIMIT
+        )));
+    }
+    
+    // Accept bare local targets like "localhost:8080/health" by defaulting to http
+    if !config.url.contains("://") {
+        config.url = format!("http://{}", config.url);
+    }
     
     // Validate URL format
     match url::Url::parse(&config.url) {
-        Ok(parsed_url) => {
-            // Only allow http and https schemes
-            if parsed_url.scheme() != "http" && parsed_url.scheme() != "https" {
+        Ok(parsed_url) => match parsed_url.scheme() {
+            "http" | "https" => {}
+            // http+unix://<percent-encoded socket path>/<request path>
+            "http+unix" => {
+                let (socket_path, url) = parse_unix_socket_url(&parsed_url)?;
+                config.unix_socket_path = Some(socket_path);
+                config.url = url;
+            }
+            scheme => {
                 return Err(LoadTestError::InvalidConfig(
-                    format!("URL must use http or https scheme, got: {}", parsed_url.scheme())
+                    format!("URL must use http, https or http+unix scheme, got: {}", scheme)
                 ));
             }
-        }
+        },
         Err(e) => {
             return Err(LoadTestError::InvalidConfig(format!("Invalid URL '{}': {}", config.url, e)));
         }
     }
     
+    // Validate Unix socket target (empty path means plain TCP)
+    config.unix_socket_path = config.unix_socket_path.take().filter(|path| !path.is_empty());
+    if let Some(socket_path) = config.unix_socket_path.as_deref() {
+        if !cfg!(unix) {
+            return Err(LoadTestError::InvalidConfig("Unix socket targets are only supported on Unix platforms".into()));
+        }
+        if !proxy_list(&config).is_empty() {
+            return Err(LoadTestError::InvalidConfig("A proxy cannot be used with a Unix socket target".into()));
+        }
+        if !std::path::Path::new(socket_path).exists() {
+            return Err(LoadTestError::InvalidConfig(format!("Unix socket not found: {}", socket_path)));
+        }
+    }
+    
+    // Validate local source addresses
+    if !config.local_addresses.is_empty() {
+        if config.unix_socket_path.is_some() {
+            return Err(LoadTestError::InvalidConfig("Local addresses cannot be used with a Unix socket target".into()));
+        }
+        for address in &config.local_addresses {
+            if !config.ip_preference.allows(address) {
+                return Err(LoadTestError::InvalidConfig(
+                    format!("Local address {} conflicts with IP preference {:?}", address, config.ip_preference)
+                ));
+            }
+        }
+    }
+    
+    // The IP preference is applied by the DNS resolver, which is skipped for IP-literal hosts,
+    // so check a literal target here (behind a proxy the proxy connects to the target)
+    let target_host = url::Url::parse(&config.url).ok().and_then(|url| url.host().map(|host| host.to_owned()));
+    let target_address = match target_host {
+        Some(url::Host::Ipv4(address)) => Some(IpAddr::V4(address)),
+        Some(url::Host::Ipv6(address)) => Some(IpAddr::V6(address)),
+        _ => None,
+    };
+    let direct = config.unix_socket_path.is_none() && proxy_list(&config).is_empty();
+    if let Some(target_address) = target_address.filter(|_| direct) {
+        validate_target_address(target_address, config.ip_preference, &config.local_addresses)?;
+    }
+    
+    // Validate request signing
+    if let Some(signing) = &config.signing {
+        if config.form_fields.as_ref().is_some_and(|fields| !fields.is_empty()) {
+            return Err(LoadTestError::InvalidConfig("Request signing is not supported with multipart form data".into()));
+        }
+        if matches!(signing, SigningConfig::AwsSigV4 { .. }) && config.auth.is_some() {
+            return Err(LoadTestError::InvalidConfig(
+                "AWS SigV4 signing sets the Authorization header and cannot be combined with authentication".into()
+            ));
+        }
+    }
+    
+    // Validate request compression (the body is compressed once, so multipart is not supported)
+    if config.request_compression != BodyCompression::None {
+        if config.form_fields.as_ref().is_some_and(|fields| !fields.is_empty()) {
+            return Err(LoadTestError::InvalidConfig("Request compression is not supported with multipart form data".into()));
+        }
+        if config.headers.iter().any(|header| header.key.eq_ignore_ascii_case("Content-Encoding")) {
+            return Err(LoadTestError::InvalidConfig(
+                "Remove the custom Content-Encoding header when request compression is enabled".into()
+            ));
+        }
+    }
+    
+    if let Some(auto_stop) = &config.auto_stop {
+        auto_stop.validate()?;
+    }
+    slo::validate(&config.slo_thresholds, &config.url)?;
+    
+    if !config.warmup_secs.is_finite() || config.warmup_secs < 0.0 {
+        return Err(LoadTestError::InvalidConfig("warmup_secs must be 0 or a positive number".into()));
+    }
+    if config.warmup_requests >= config.num_requests {
+        return Err(LoadTestError::InvalidConfig("warmup_requests must be less than num_requests".into()));
+    }
+    config.think_time.validate()?;
+    if !(0.0..=MAX_PACING_SECS).contains(&config.pacing_secs) {
+        return Err(LoadTestError::InvalidConfig(format!("pacing_secs must be between 0 and {}", MAX_PACING_SECS)));
+    }
+    if !STATS_WINDOW_SECS_RANGE.contains(&config.stats_window_secs) {
+        return Err(LoadTestError::InvalidConfig(format!(
+            "stats_window_secs must be between {} and {}",
+            STATS_WINDOW_SECS_RANGE.start(),
+            STATS_WINDOW_SECS_RANGE.end()
+        )));
+    }
+    
+    // Validate response capture
+    if let Some(capture) = &config.response_capture {
+        if !(0.0..=1.0).contains(&capture.success_sample_rate) {
+            return Err(LoadTestError::InvalidConfig("Success sample rate must be between 0 and 1".into()));
+        }
+    }
+    
+    // Validate HTTP/2 negotiation mode against the URL scheme
+    if config.use_http2 {
+        let is_https = config.url.starts_with("https://");
+        match config.http2_mode {
+            Http2Mode::Upgrade if is_https => {
+                return Err(LoadTestError::InvalidConfig("h2c upgrade requires an http URL (use ALPN for https)".into()));
+            }
+            Http2Mode::Upgrade if !proxy_list(&config).is_empty() => {
+                return Err(LoadTestError::InvalidConfig(
+                    "h2c upgrade cannot be used with a proxy (the handshake is made directly to the target)".into(),
+                ));
+            }
+            Http2Mode::Alpn if !is_https => {
+                return Err(LoadTestError::InvalidConfig("ALPN negotiation requires an https URL".into()));
+            }
+            _ => {}
+        }
+    }
+    
     // Determine number of worker threads
     let default_threads = std::thread::available_parallelism()
         .map(|n| n.get())
@@ -1008,39 +2377,311 @@ async fn run_load_test(app_handle: AppHandle, config: LoadTestConfig) -> Result<
         config.worker_threads as usize
     };
     
-    // Increment test generation to invalidate any previous test's cancellation state
-    // This prevents lingering async tasks from a cancelled test from affecting new tests
-    let current_generation = TEST_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    config.concurrency = effective_concurrency(config.concurrency, config.num_requests);
     
-    // Reset cancellation flag and progress throttle at start
-    CANCEL_FLAG.store(false, Ordering::SeqCst);
-    LAST_PROGRESS_MS.store(0, Ordering::Relaxed);
+    // Register the run; its state is scoped to this call, so lingering tasks of a cancelled
+    // run cannot affect other runs. The guard unregisters the run when this call returns.
+    let guard = runs::register(config.url.clone(), config.num_requests, config.concurrency, config.rate_limit);
+    let run = Arc::clone(&guard.0);
+    let started = RunStarted { url: config.url.clone(), client_token: config.client_token.clone() };
+    let _ = app_handle.emit("load-test-started", RunEvent { run_id: run.id, payload: started });
+    let started_at_ms = run.started_at_ms();
+    let history_config = config.save_to_history.then(|| history::redacted_config(&config));
+    let omit_results = config.omit_results;
     
     // If using custom thread count, spawn a dedicated runtime
-    if config.worker_threads > 0 && (config.worker_threads as usize) != default_threads {
+    let mut stats = if config.worker_threads > 0 && (config.worker_threads as usize) != default_threads {
         let app_handle_clone = app_handle.clone();
-        let result = tokio::task::spawn_blocking(move || {
+        tokio::task::spawn_blocking(move || {
             let rt = tokio::runtime::Builder::new_multi_thread()
                 .worker_threads(worker_threads)
                 .enable_all()
                 .build()
                 .map_err(|e| LoadTestError::Internal(format!("Failed to create runtime: {}", e)))?;
             
-            rt.block_on(run_load_test_inner(app_handle_clone, config, current_generation))
+            rt.block_on(run_load_test_inner(app_handle_clone, config, run))
         })
         .await
-        .map_err(|e| LoadTestError::Internal(format!("Task join error: {}", e)))?;
-        
-        return result;
+        .map_err(|e| LoadTestError::Internal(format!("Task join error: {}", e)))??
+    } else {
+        // Use default runtime
+        run_load_test_inner(app_handle.clone(), config, run).await?
+    };
+    drop(guard);
+    
+    // A failed save is reported with the stats rather than failing the finished run
+    if let Some(history_config) = history_config {
+        let (saved_stats, saved) = history::save_run(&app_handle, history_config, started_at_ms, stats).await;
+        stats = saved_stats;
+        match saved {
+            Ok(id) => stats.history_id = Some(id),
+            Err(e) => stats.history_error = Some(e.to_string()),
+        }
+    }
+    if omit_results {
+        stats.results = Vec::new();
     }
+    Ok(stats)
+}
+
+/// Splits an `http+unix://` URL into the socket path (the percent-encoded host) and the
+/// equivalent `http://localhost` URL used for the request line and Host header.
+fn parse_unix_socket_url(parsed_url: &url::Url) -> Result<(String, String), LoadTestError> {
+    let encoded_path = parsed_url.host_str().unwrap_or_default();
+    let socket_path = percent_encoding::percent_decode_str(encoded_path)
+        .decode_utf8()
+        .map_err(|e| LoadTestError::InvalidConfig(format!("Invalid Unix socket path '{}': {}", encoded_path, e)))?;
+    if socket_path.is_empty() {
+        return Err(LoadTestError::InvalidConfig(
+            "http+unix URL must contain a percent-encoded socket path, e.g. http+unix://%2Fvar%2Frun%2Fapi.sock/health".into()
+        ));
+    }
+    
+    let mut url = format!("http://localhost{}", parsed_url.path());
+    if let Some(query) = parsed_url.query() {
+        url.push('?');
+        url.push_str(query);
+    }
+    Ok((socket_path.into_owned(), url))
+}
+
+/// Performs a one-off `Upgrade: h2c` handshake against the target and returns whether the
+/// server switched protocols. reqwest cannot take over an upgraded connection, so when the
+/// upgrade is accepted the load itself runs over h2c (prior knowledge) connections.
+async fn probe_h2c_upgrade(config: &LoadTestConfig) -> Result<bool, LoadTestError> {
+    let url = url::Url::parse(&config.url)
+        .map_err(|e| LoadTestError::InvalidConfig(format!("Invalid URL '{}': {}", config.url, e)))?;
+    let host = url.host_str().unwrap_or("localhost");
+    let port = url.port_or_known_default().unwrap_or(80);
+    let host_header = match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    };
+    let path = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+    let request = format!(
+        "OPTIONS {} HTTP/1.1\r\nHost: {}\r\nConnection: Upgrade, HTTP2-Settings\r\nUpgrade: h2c\r\nHTTP2-Settings: {}\r\n\r\n",
+        path, host_header, H2C_UPGRADE_SETTINGS
+    );
+    
+    let probe = async {
+        #[cfg(unix)]
+        if let Some(socket_path) = &config.unix_socket_path {
+            let stream = tokio::net::UnixStream::connect(socket_path).await?;
+            return read_upgrade_status(stream, &request).await;
+        }
+        let stream = connect_probe(config, &format!("{}:{}", host, port)).await?;
+        read_upgrade_status(stream, &request).await
+    };
+    
+    let timeout = if config.timeout_secs > 0.0 {
+        Duration::from_secs_f64(config.timeout_secs)
+    } else {
+        Duration::from_secs(30)
+    };
+    let status = tokio::time::timeout(timeout, probe)
+        .await
+        .map_err(|_| LoadTestError::InvalidConfig("h2c upgrade probe timed out".into()))?
+        .map_err(|e| LoadTestError::InvalidConfig(format!("h2c upgrade probe failed: {}", e)))?;
+    
+    Ok(status == 101)
+}
+
+/// Connects to `target` ("host:port") the way the run's first client would: from its local
+/// source address, if any, and to the address families allowed by the IP preference
+async fn connect_probe(config: &LoadTestConfig, target: &str) -> std::io::Result<tokio::net::TcpStream> {
+    let local_address = config.local_addresses.first().copied();
+    let preference = route_ip_preference(local_address, config.ip_preference);
+    let mut addrs: Vec<SocketAddr> = tokio::net::lookup_host(target).await?.collect();
+    apply_ip_preference(&mut addrs, preference);
+    
+    let mut last_error = std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!("No {:?} addresses found for {}", preference, target),
+    );
+    for addr in addrs {
+        let socket = if addr.is_ipv4() { tokio::net::TcpSocket::new_v4()? } else { tokio::net::TcpSocket::new_v6()? };
+        if let Some(local_address) = local_address {
+            socket.bind(SocketAddr::new(local_address, 0))?;
+        }
+        match socket.connect(addr).await {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
+
+/// Sends a raw HTTP/1.1 request and parses the status code from the response status line
+async fn read_upgrade_status<S: AsyncRead + AsyncWrite + Unpin>(mut stream: S, request: &str) -> std::io::Result<u16> {
+    stream.write_all(request.as_bytes()).await?;
+    
+    let mut head = Vec::with_capacity(256);
+    let mut buf = [0u8; 256];
+    while !head.contains(&b'\n') && head.len() < H2C_UPGRADE_MAX_RESPONSE {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+    
+    // Status line: "HTTP/1.1 101 Switching Protocols"
+    String::from_utf8_lossy(&head)
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "malformed HTTP response"))
+}
+
+/// Returns all configured proxies: `proxy_url` followed by the rotation list, blanks removed
+fn proxy_list(config: &LoadTestConfig) -> Vec<String> {
+    std::iter::once(&config.proxy_url)
+        .chain(&config.proxy_urls)
+        .map(|proxy| proxy.trim())
+        .filter(|proxy| !proxy.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Normalizes a proxy address to a URL, defaulting to the http scheme for "host:port"
+fn normalize_proxy_url(proxy: &str) -> String {
+    if proxy.contains("://") {
+        proxy.to_string()
+    } else {
+        format!("http://{}", proxy)
+    }
+}
+
+/// Strips credentials from a proxy address so it can be shown in results
+fn redact_proxy_url(proxy: &str) -> String {
+    match url::Url::parse(&normalize_proxy_url(proxy)) {
+        Ok(mut url) if !url.username().is_empty() || url.password().is_some() => {
+            let _ = url.set_username("");
+            let _ = url.set_password(None);
+            url.to_string()
+        }
+        _ => proxy.to_string(),
+    }
+}
+
+/// DNS resolver that orders or filters the resolved addresses by IP family
+struct IpPreferenceResolver {
+    preference: IpPreference,
+}
+
+impl reqwest::dns::Resolve for IpPreferenceResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let preference = self.preference;
+        Box::pin(async move {
+            let mut addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            apply_ip_preference(&mut addrs, preference);
+            if addrs.is_empty() {
+                return Err(format!("No {:?} addresses found for {}", preference, name.as_str()).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+/// IP preference of connections from `local_address`, which can only reach its own family
+fn route_ip_preference(local_address: Option<IpAddr>, configured: IpPreference) -> IpPreference {
+    match local_address {
+        Some(IpAddr::V4(_)) => IpPreference::V4Only,
+        Some(IpAddr::V6(_)) => IpPreference::V6Only,
+        None => configured,
+    }
+}
+
+/// Orders or filters resolved addresses by IP family. Stable sorts keep the system resolver's
+/// order within each family.
+fn apply_ip_preference(addrs: &mut Vec<SocketAddr>, preference: IpPreference) {
+    match preference {
+        IpPreference::Any => {}
+        IpPreference::PreferV4 => addrs.sort_by_key(|addr| !addr.is_ipv4()),
+        IpPreference::PreferV6 => addrs.sort_by_key(|addr| !addr.is_ipv6()),
+        IpPreference::V4Only => addrs.retain(SocketAddr::is_ipv4),
+        IpPreference::V6Only => addrs.retain(SocketAddr::is_ipv6),
+    }
+}
+
+/// Where a client's connections originate from and go through
+#[derive(Clone, Copy)]
+struct ClientRoute<'a> {
+    proxy_index: Option<u32>,
+    proxy: Option<&'a str>,
+    local_address: Option<IpAddr>,
+}
+
+/// Builds the clients for a test run: one client per proxy and local address combination,
+/// and one client per connection for multiplexed HTTP/2 so requests are spread over
+/// exactly `http2_connections` connections (per route).
+fn build_client_pool(
+    config: &LoadTestConfig,
+    concurrency: u32,
+    http2_prior_knowledge: bool,
+    connections_opened: &Arc<AtomicU32>,
+    shared_cookies: Option<&Arc<reqwest::cookie::Jar>>,
+) -> Result<Vec<ClientSlot>, LoadTestError> {
+    let proxies = proxy_list(config);
+    let connections_per_proxy = if config.use_http2 && config.http2_connections > 0 {
+        config.http2_connections
+    } else {
+        1
+    };
+    let max_streams = if config.use_http2 { config.http2_max_streams_per_connection } else { 0 };
+    
+    // Without proxies or local addresses there is a single "direct" route
+    let proxy_routes: Vec<(Option<u32>, Option<&str>)> = if proxies.is_empty() {
+        vec![(None, None)]
+    } else {
+        proxies.iter().enumerate().map(|(i, proxy)| (Some(i as u32), Some(proxy.as_str()))).collect()
+    };
+    let local_addresses: Vec<Option<IpAddr>> = if config.local_addresses.is_empty() {
+        vec![None]
+    } else {
+        config.local_addresses.iter().copied().map(Some).collect()
+    };
+    let routes: Vec<ClientRoute> = proxy_routes
+        .iter()
+        .flat_map(|&(proxy_index, proxy)| {
+            local_addresses.iter().map(move |&local_address| ClientRoute { proxy_index, proxy, local_address })
+        })
+        .collect();
     
-    // Use default runtime
-    run_load_test_inner(app_handle, config, current_generation).await
+    let mut slots = Vec::with_capacity(routes.len() * connections_per_proxy as usize);
+    for _ in 0..connections_per_proxy {
+        for route in &routes {
+            slots.push(ClientSlot {
+                client: build_http_client(
+                    config,
+                    concurrency,
+                    http2_prior_knowledge,
+                    connections_opened,
+                    shared_cookies,
+                    route,
+                )?,
+                stream_limit: (max_streams > 0).then(|| Arc::new(Semaphore::new(max_streams as usize))),
+                proxy_index: route.proxy_index,
+            });
+        }
+    }
+    Ok(slots)
 }
 
 /// Builds an HTTP client with the specified configuration
-fn build_http_client(config: &LoadTestConfig, concurrency: u32) -> Result<reqwest::Client, LoadTestError> {
+fn build_http_client(
+    config: &LoadTestConfig,
+    concurrency: u32,
+    http2_prior_knowledge: bool,
+    connections_opened: &Arc<AtomicU32>,
+    shared_cookies: Option<&Arc<reqwest::cookie::Jar>>,
+    route: &ClientRoute,
+) -> Result<reqwest::Client, LoadTestError> {
     let mut builder = reqwest::Client::builder()
+        .connector_layer(ConnectionCountLayer { opened: Arc::clone(connections_opened) })
         .tcp_nodelay(true)
         // TCP keep-alive to prevent connections from being silently closed by routers/firewalls
         // This is different from HTTP keep-alive - it sends TCP-level probes to keep connections alive
@@ -1053,11 +2694,6 @@ fn build_http_client(config: &LoadTestConfig, concurrency: u32) -> Result<reqwes
         // Disable connection pooling entirely - each request gets a fresh connection
         // pool_max_idle_per_host(0) ensures connections are not reused after going idle
         builder = builder.pool_max_idle_per_host(0);
-        // Force HTTP/1.1 when keep-alive is disabled to support Connection: close header
-        // (HTTP/2 handles connection management differently and doesn't use Connection header)
-        if !config.use_http2 {
-            builder = builder.http1_only();
-        }
     } else {
         // Enable connection pooling for better performance
         // Pool size should match concurrency to avoid connection contention
@@ -1076,29 +2712,58 @@ fn build_http_client(config: &LoadTestConfig, concurrency: u32) -> Result<reqwes
         builder = builder.timeout(Duration::from_secs_f64(config.timeout_secs));
     }
     
-    // Configure HTTP version (only if not already set by keep-alive logic)
-    if config.use_http2 {
+    // Configure HTTP version: prior knowledge speaks HTTP/2 directly and ALPN lets TLS negotiate.
+    // Everything else (including a declined h2c upgrade) is HTTP/1.1 only, which also keeps the
+    // Connection: close header meaningful when keep-alive is disabled
+    let alpn = config.use_http2 && config.http2_mode == Http2Mode::Alpn;
+    if http2_prior_knowledge {
         builder = builder.http2_prior_knowledge();
-    } else if !config.disable_keep_alive {
-        // Only set http1_only if we haven't already set it for keep-alive
+    } else if !alpn {
         builder = builder.http1_only();
     }
     
+    // Configure cookie store (also applied to redirect hops, e.g. login flows)
+    match (config.cookie_mode, shared_cookies) {
+        (CookieMode::Shared, Some(jar)) => builder = builder.cookie_provider(Arc::clone(jar)),
+        (CookieMode::PerVirtualUser, _) => builder = builder.cookie_provider(Arc::new(VirtualUserCookies)),
+        _ => {}
+    }
+    
     // Configure redirect policy
     if !config.follow_redirects {
         builder = builder.redirect(reqwest::redirect::Policy::none());
     }
     
-    // Configure HTTP proxy
-    if !config.proxy_url.is_empty() {
-        let proxy_url = if config.proxy_url.starts_with("http://") || config.proxy_url.starts_with("https://") {
-            config.proxy_url.clone()
-        } else {
-            format!("http://{}", config.proxy_url)
-        };
+    // Route all connections through a Unix domain socket (replaces TCP connect and DNS)
+    #[cfg(unix)]
+    if let Some(socket_path) = &config.unix_socket_path {
+        builder = builder.unix_socket(socket_path.as_str());
+    }
+    
+    // Bind to a local source address; the target must then resolve to the same IP family
+    let ip_preference = route_ip_preference(route.local_address, config.ip_preference);
+    if let Some(local_address) = route.local_address {
+        builder = builder.local_address(local_address);
+    }
+    if ip_preference != IpPreference::Any {
+        builder = builder.dns_resolver(Arc::new(IpPreferenceResolver { preference: ip_preference }));
+    }
+    
+    // Configure proxy (HTTP, HTTPS or SOCKS5; socks5h resolves DNS on the proxy)
+    if let Some(proxy) = route.proxy {
+        let proxy_url = normalize_proxy_url(proxy);
+        let mut proxy = reqwest::Proxy::all(&proxy_url)
+            .map_err(|e| LoadTestError::InvalidConfig(format!("Invalid proxy URL '{}': {}", redact_proxy_url(&proxy_url), e)))?;
+        
+        // Credentials embedded in the proxy URL take precedence over the shared ones
+        let has_url_credentials = url::Url::parse(&proxy_url).is_ok_and(|url| !url.username().is_empty());
+        if let (Some(username), false) = (config.proxy_username.as_deref(), has_url_credentials) {
+            proxy = proxy.basic_auth(username, config.proxy_password.as_deref().unwrap_or_default());
+        }
         
-        let proxy = reqwest::Proxy::all(&proxy_url)
-            .map_err(|e| LoadTestError::InvalidConfig(format!("Invalid proxy URL '{}': {}", proxy_url, e)))?;
+        if !config.no_proxy.is_empty() {
+            proxy = proxy.no_proxy(reqwest::NoProxy::from_string(&config.no_proxy.join(",")));
+        }
         builder = builder.proxy(proxy);
     }
     
@@ -1108,19 +2773,50 @@ fn build_http_client(config: &LoadTestConfig, concurrency: u32) -> Result<reqwes
 async fn run_load_test_inner(
     app_handle: AppHandle,
     config: LoadTestConfig,
-    current_generation: u64,
+    run: Arc<RunHandle>,
 ) -> Result<LoadTestStats, LoadTestError> {
-    // Concurrency control - default to num_requests if concurrency is 0 or greater than num_requests
-    let concurrency = if config.concurrency == 0 || config.concurrency > config.num_requests {
-        config.num_requests
-    } else {
-        config.concurrency
+    // Normalized by run_load_test
+    let concurrency = config.concurrency;
+    
+    // Resolve HTTP/2 negotiation - an accepted h2c upgrade continues as h2c, a declined one as HTTP/1.1
+    let http2_prior_knowledge = match (config.use_http2, config.http2_mode) {
+        (false, _) | (true, Http2Mode::Alpn) => false,
+        (true, Http2Mode::PriorKnowledge) => true,
+        (true, Http2Mode::Upgrade) => probe_h2c_upgrade(&config).await?,
     };
     
-    // Build HTTP client
-    let client = build_http_client(&config, concurrency)?;
+    // The run's counters (the connection counter is wired into the clients' connectors)
+    let counters = Arc::clone(&run.counters);
+    
+    // Seed cookies are scoped to the target URL unless they carry a Domain attribute
+    let cookie_url = url::Url::parse(&config.url)
+        .map_err(|e| LoadTestError::InvalidConfig(format!("Invalid URL '{}': {}", config.url, e)))?;
+    let seed_cookies = if config.cookie_mode == CookieMode::Disabled {
+        Vec::new()
+    } else {
+        config.seed_cookies.clone()
+    };
+    let shared_cookies = (config.cookie_mode == CookieMode::Shared).then(|| new_cookie_jar(&seed_cookies, &cookie_url));
+    
+    // Build HTTP clients (one per proxy when rotating)
+    let proxies = proxy_list(&config);
+    let clients = build_client_pool(
+        &config,
+        concurrency,
+        http2_prior_knowledge,
+        &counters.connections_opened,
+        shared_cookies.as_ref(),
+    )?;
 
+    // Set up authentication (fetches the initial OAuth2 token before the clock starts)
+    let auth = match config.auth {
+        Some(auth_config) => Some(Authenticator::new(auth_config).await?),
+        None => None,
+    };
+    let signer = config.signing.map(signing::build_signer).transpose()?;
+    
     let start = Instant::now();
+    run.mark_started(start);
     
     // Use channel for result collection to reduce mutex contention
     let (result_tx, mut result_rx) = mpsc::unbounded_channel::<RequestResult>();
@@ -1129,13 +2825,6 @@ async fn run_load_test_inner(
     let results_capacity = config.num_requests as usize;
     let num_requests = config.num_requests;
     
-    // Calculate rate limit interval (if rate_limit > 0, interval = 1/rate_limit seconds)
-    let rate_limit_interval = if config.rate_limit > 0.0 {
-        Some(Duration::from_secs_f64(1.0 / config.rate_limit))
-    } else {
-        None
-    };
-    
     // Process form fields if present (load file contents)
     let form_fields: Option<Arc<[FormField]>> = if let Some(fields) = config.form_fields {
         if !fields.is_empty() {
@@ -1180,12 +2869,23 @@ async fn run_load_test_inner(
         config.body.map(Bytes::from)
     };
     
+    // Compress the body once; every request sends the same encoded bytes
+    let request_body_bytes = body_bytes.as_ref().map_or(0, |body| body.len() as u64);
+    let content_encoding = config.request_compression.content_encoding().filter(|_| request_body_bytes > 0);
+    let body_bytes = match (&body_bytes, content_encoding) {
+        (Some(body), Some(_)) => Some(Bytes::from(
+            compression::compress(body, config.request_compression)
+                .map_err(|e| LoadTestError::InvalidConfig(format!("Failed to compress request body: {}", e)))?,
+        )),
+        _ => body_bytes,
+    };
+    let request_wire_bytes = body_bytes.as_ref().map_or(0, |body| body.len() as u64);
+    
     // Create test config with immutable settings
     let test_config = Arc::new(TestConfig {
         url: config.url.into(),
         method: config.method,
         headers: config.headers.into(),
-        rate_limit_interval,
         randomize_user_agent: config.randomize_user_agent,
         randomize_headers: config.randomize_headers,
         add_cache_buster: config.add_cache_buster,
@@ -1193,83 +2893,215 @@ async fn run_load_test_inner(
         payload_content_type: config.payload_content_type,
         disable_keep_alive: config.disable_keep_alive,
         form_fields,
+        content_encoding,
+        decompress_responses: config.decompress_responses,
+        response_capture: config.response_capture,
+        think_time: config.think_time,
+        pacing: (config.pacing_secs > 0.0).then(|| Duration::from_secs_f64(config.pacing_secs)),
     });
     
-    // Create shared counters
-    let counters = Arc::new(TestCounters::new());
-    
-    // Create shared request context using the generation passed from run_load_test
+    // Create shared request context for the run registered by run_load_test
     let base_ctx = Arc::new(RequestContext {
-        client,
+        clients: clients.into(),
+        next_client: AtomicUsize::new(0),
+        auth,
+        signer,
         config: test_config,
-        counters,
+        counters: Arc::clone(&counters),
+        run,
         app_handle,
         total: num_requests,
         start_time: start,
-        cancel_flag: &CANCEL_FLAG,
-        test_generation: current_generation,
     });
     
-    // Use buffer_unordered to control concurrency efficiently
-    // This only creates `concurrency` futures at a time, avoiding the memory pressure
-    // of creating all futures upfront with join_all + semaphore
-    {
-        // Scope the sender so it's dropped when the stream completes
-        let request_stream = stream::iter(0..num_requests)
-            .map(|_| {
-                let ctx = Arc::clone(&base_ctx);
-                let tx = result_tx.clone();
-                
-                async move {
-                    // Check cancellation before starting
-                    if is_cancelled(ctx.cancel_flag, ctx.test_generation) {
-                        return;
-                    }
-                    
-                    // Apply rate limiting delay if configured
-                    if let Some(interval) = ctx.config.rate_limit_interval {
-                        // Simple cancellation check during rate limit - no complex select! loop needed
-                        tokio::select! {
-                            biased;
-                            _ = tokio::time::sleep(interval) => {}
-                            _ = async {
-                                while !is_cancelled(ctx.cancel_flag, ctx.test_generation) {
-                                    tokio::time::sleep(Duration::from_millis(CANCEL_POLL_MS)).await;
-                                }
-                            } => {
-                                return;
-                            }
-                        }
+    // Run `concurrency` workers (virtual users) that each claim the next request index
+    // until all requests are issued. Only `concurrency` futures exist at a time, avoiding
+    // the memory pressure of creating all request futures upfront. The worker count follows
+    // the live concurrency target: workers above it retire, free slots below it are refilled
+    let dispatch_ctx = Arc::clone(&base_ctx);
+    let per_user_cookies = config.cookie_mode == CookieMode::PerVirtualUser;
+    let dispatch = async move {
+        let base_ctx = dispatch_ctx;
+        let next_request = Arc::new(AtomicU32::new(0));
+        let mut workers = FuturesUnordered::new();
+        let mut active_slots: Vec<bool> = Vec::new();
+        
+        loop {
+            let target = (base_ctx.run.control.concurrency.load(Ordering::Relaxed) as usize).min(num_requests as usize);
+            if active_slots.len() < target {
+                active_slots.resize(target, false);
+            }
+            if next_request.load(Ordering::Relaxed) < num_requests && !base_ctx.run.is_stopping() {
+                for (worker_id, active) in active_slots.iter_mut().enumerate().take(target) {
+                    if !*active {
+                        *active = true;
+                        let worker = run_worker(
+                            Arc::clone(&base_ctx),
+                            result_tx.clone(),
+                            Arc::clone(&next_request),
+                            worker_id,
+                        );
+                        // In per-virtual-user mode each worker has its own cookie jar, read by the clients
+                        workers.push(if per_user_cookies {
+                            Either::Left(SESSION_JAR.scope(new_cookie_jar(&seed_cookies, &cookie_url), worker))
+                        } else {
+                            Either::Right(worker)
+                        });
                     }
-                    
-                    let _ = make_request(&ctx, &tx).await;
                 }
-            })
-            .buffer_unordered(concurrency as usize);
-        
-        // Execute all requests with controlled concurrency
-        // buffer_unordered ensures only `concurrency` futures run at a time
-        request_stream.for_each(|_| async {}).await;
+            }
+            
+            // Done once every worker has finished (all requests issued, or stopped)
+            if workers.is_empty() {
+                break;
+            }
+            
+            // Wait for a worker to finish, polling periodically for concurrency changes
+            tokio::select! {
+                Some(worker_id) = workers.next() => active_slots[worker_id] = false,
+                _ = tokio::time::sleep(Duration::from_millis(CANCEL_POLL_MS)) => {}
+            }
+        }
         
-        // Drop the original sender so channel closes when stream completes
+        // Drop the original sender so channel closes when all workers complete
         drop(result_tx);
-    }
+    };
     
-    let total_time = start.elapsed();
-    let total_time_secs = total_time.as_secs_f64();
+    // Collect results from channel while the workers run, marking warm-up results, emitting
+    // progress, streaming window aggregates as windows close and checking auto-stop
+    // thresholds once warm-up is over
+    let warmup_ms = config.warmup_secs * 1000.0;
+    let warmup_requests = config.warmup_requests as usize;
+    let window_secs = config.stats_window_secs;
+    let collect = async {
+        let mut results = Vec::with_capacity(results_capacity);
+        let mut monitor = config.auto_stop.map(AutoStopMonitor::new);
+        let mut stop_reason = None;
+        let mut windows = WindowAggregator::new(window_secs);
+        let emit_window = |window: WindowStats| {
+            let _ = base_ctx.app_handle.emit("load-test-window", RunEvent { run_id: base_ctx.run.id, payload: window });
+        };
+        let mut progress = ProgressAggregator::new();
+        let emit_progress = |progress: &mut ProgressAggregator| {
+            let elapsed = start.elapsed().as_secs_f64();
+            let active = elapsed - base_ctx.run.paused_duration().as_secs_f64();
+            let update = progress.update(base_ctx.run.id, num_requests, &counters, elapsed, active);
+            let _ = base_ctx.app_handle.emit("load-test-progress", update);
+        };
+        let mut progress_tick = tokio::time::interval(Duration::from_millis(PROGRESS_THROTTLE_MS));
+        progress_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            // Close the open window on time even when no results arrive (e.g. a stalled target)
+            let window_end = tokio::time::Instant::from_std(start) + Duration::from_secs_f64(windows.open_window_end_secs());
+            let mut result = tokio::select! {
+                result = result_rx.recv() => match result {
+                    Some(result) => result,
+                    None => break,
+                },
+                _ = tokio::time::sleep_until(window_end) => {
+                    emit_window(windows.close());
+                    continue;
+                }
+                _ = progress_tick.tick() => {
+                    emit_progress(&mut progress);
+                    continue;
+                }
+            };
+            progress.observe(&result);
+            
+            result.warmup = results.len() < warmup_requests || result.timestamp_ms - result.duration_ms < warmup_ms;
+            windows.observe(&result).into_iter().for_each(emit_window);
+            if result.warmup {
+                results.push(result);
+                continue;
+            }
+            if let Some(reason) = monitor.as_mut().and_then(|monitor| monitor.observe(&result)) {
+                // Stop through the cancellation path; workers drain as on a user cancel
+                base_ctx.run.cancel(false);
+                let _ = base_ctx.app_handle.emit(
+                    "load-test-auto-stopped",
+                    RunEvent { run_id: base_ctx.run.id, payload: reason.clone() },
+                );
+                stop_reason = Some(reason);
+                monitor = None;
+            }
+            results.push(result);
+        }
+        if let Some(window) = windows.finish() {
+            emit_window(window);
+        }
+        emit_progress(&mut progress);
+        (results, stop_reason)
+    };
     
-    // Collect results from channel
-    let mut results = Vec::with_capacity(results_capacity);
-    while let Some(result) = result_rx.recv().await {
-        results.push(result);
-    }
+    let ((), (results, stop_reason)) = tokio::join!(dispatch, collect);
+    
+    let end = Instant::now();
+    let total_time_secs = end.duration_since(start).as_secs_f64();
+    let pause_intervals = base_ctx.run.pause_intervals(end);
     
     // Calculate and return statistics
-    Ok(calculate_stats(results, num_requests, total_time_secs))
+    let mut stats = calculate_stats(results, num_requests, total_time_secs, &pause_intervals, window_secs);
+    stats.run_id = base_ctx.run.id;
+    stats.annotations = base_ctx.run.control.annotations.lock().map(|a| a.clone()).unwrap_or_default();
+    stats.cancelled = stop_reason.is_none() && base_ctx.run.is_stopping();
+    stats.stop_reason = stop_reason;
+    stats.connections_opened = counters.connections_opened.load(Ordering::Relaxed);
+    stats.http_version = counters.http_version.get().map(|version| format!("{:?}", version));
+    stats.proxy_stats = calculate_proxy_stats(&stats.results, &proxies);
+    stats.auth_stats = base_ctx.auth.as_ref().map(Authenticator::stats);
+    if content_encoding.is_some() || base_ctx.config.decompress_responses {
+        let mut compression = calculate_compression_stats(&stats.results);
+        compression.request_encoding = content_encoding.map(String::from);
+        compression.request_body_bytes = request_body_bytes;
+        compression.request_wire_bytes = request_wire_bytes;
+        stats.compression = Some(compression);
+    }
+    if let Some(capture) = &base_ctx.config.response_capture {
+        let mut capture_stats = CaptureStats {
+            captured: counters.captured_responses.load(Ordering::Relaxed),
+            dropped_over_budget: counters.captures_dropped.load(Ordering::Relaxed),
+            bytes_used: counters.capture_bytes_used.load(Ordering::Relaxed),
+        };
+        charge_capture_copies(&mut stats, capture.memory_budget_bytes, &mut capture_stats);
+        stats.capture_stats = Some(capture_stats);
+    }
+    if !config.slo_thresholds.is_empty() {
+        stats.slo_verdict = Some(slo::evaluate(&config.slo_thresholds, &stats));
+    }
+    Ok(stats)
 }
 
 /// Calculates all statistics from the collected request results
-fn calculate_stats(results: Vec<RequestResult>, num_requests: u32, total_time_secs: f64) -> LoadTestStats {
+fn calculate_stats(
+    results: Vec<RequestResult>,
+    num_requests: u32,
+    total_time_secs: f64,
+    pause_intervals: &[PauseInterval],
+    window_secs: f64,
+) -> LoadTestStats {
+    // Warm-up results stay in the time series but not in the aggregates; the measured
+    // phase starts with the first request sent after warm-up
+    let warmup_requests = results.iter().filter(|r| r.warmup).count() as u32;
+    let measurement_start_secs = if warmup_requests == 0 {
+        0.0
+    } else {
+        results
+            .iter()
+            .filter(|r| !r.warmup)
+            .map(|r| (r.timestamp_ms - r.duration_ms).max(0.0) / 1000.0)
+            .min_by(|a, b| a.total_cmp(b))
+            .unwrap_or(total_time_secs)
+    };
+    
+    // Rates are computed over active (unpaused) time of the measured phase; charts keep wall time
+    let paused_time_secs: f64 = pause_intervals.iter().map(|pause| pause.end_secs - pause.start_secs).sum();
+    let measured_paused_secs: f64 = pause_intervals
+        .iter()
+        .map(|pause| (pause.end_secs - pause.start_secs.max(measurement_start_secs)).max(0.0))
+        .sum();
+    let active_time_secs = (total_time_secs - measurement_start_secs - measured_paused_secs).max(0.0);
+    
     // Calculate basic stats in a single pass
     let mut successful_requests = 0u32;
     let mut failed_requests = 0u32;
@@ -1277,10 +3109,12 @@ fn calculate_stats(results: Vec<RequestResult>, num_requests: u32, total_time_se
     let mut min_response_time = f64::INFINITY;
     let mut max_response_time = f64::NEG_INFINITY;
     let mut status_map: HashMap<u16, u32> = HashMap::with_capacity(STATUS_MAP_CAPACITY);
+    let mut total_bytes_sent = 0u64;
+    let mut total_bytes_received = 0u64;
     let mut error_logs: Vec<ErrorLogEntry> = Vec::with_capacity(ERROR_LOGS_MAX.min(results.len()));
     
     // Single pass through results for basic stats
-    for result in &results {
+    for result in results.iter().filter(|r| !r.warmup) {
         if result.success {
             successful_requests += 1;
         } else {
@@ -1295,6 +3129,7 @@ fn calculate_stats(results: Vec<RequestResult>, num_requests: u32, total_time_se
                     error: error_msg,
                     error_type: result.error_type.clone(),
                     duration_ms: result.duration_ms,
+                    response: result.captured_response.clone(),
                 });
             }
         }
@@ -1303,10 +3138,13 @@ fn calculate_stats(results: Vec<RequestResult>, num_requests: u32, total_time_se
         min_response_time = min_response_time.min(result.duration_ms);
         max_response_time = max_response_time.max(result.duration_ms);
         *status_map.entry(result.status).or_insert(0) += 1;
+        total_bytes_sent += result.request_bytes;
+        total_bytes_received += result.response_bytes;
     }
     
-    let avg_response_time = if !results.is_empty() {
-        sum_response_time / results.len() as f64
+    let measured_requests = successful_requests + failed_requests;
+    let avg_response_time = if measured_requests > 0 {
+        sum_response_time / measured_requests as f64
     } else {
         0.0
     };
@@ -1314,14 +3152,22 @@ fn calculate_stats(results: Vec<RequestResult>, num_requests: u32, total_time_se
     let min_response_time = if min_response_time.is_infinite() { 0.0 } else { min_response_time };
     let max_response_time = if max_response_time.is_infinite() { 0.0 } else { max_response_time };
     
-    let requests_per_second = if total_time_secs > 0.0 {
-        results.len() as f64 / total_time_secs
+    let requests_per_second = if active_time_secs > 0.0 {
+        measured_requests as f64 / active_time_secs
     } else {
         0.0
     };
+    let (sent_mb_per_sec, received_mb_per_sec) = if active_time_secs > 0.0 {
+        (
+            total_bytes_sent as f64 / BYTES_PER_MB / active_time_secs,
+            total_bytes_received as f64 / BYTES_PER_MB / active_time_secs,
+        )
+    } else {
+        (0.0, 0.0)
+    };
     
     // Calculate histogram and percentiles
-    let response_times: Vec<f64> = results.iter().map(|r| r.duration_ms).collect();
+    let response_times: Vec<f64> = results.iter().filter(|r| !r.warmup).map(|r| r.duration_ms).collect();
     let histogram = build_histogram(&response_times, min_response_time, max_response_time, HISTOGRAM_BUCKETS);
     
     let mut sorted_times = response_times;
@@ -1337,6 +3183,22 @@ fn calculate_stats(results: Vec<RequestResult>, num_requests: u32, total_time_se
         p99: calculate_percentile(&sorted_times, 99.0),
     };
     
+    // Response sizes, skipping requests that never got a response
+    let mut sorted_sizes: Vec<f64> = results
+        .iter()
+        .filter(|r| !r.warmup && r.response_bytes > 0)
+        .map(|r| r.response_bytes as f64)
+        .collect();
+    sorted_sizes.sort_by(|a, b| a.total_cmp(b));
+    let response_size_percentiles = SizePercentiles {
+        avg: if sorted_sizes.is_empty() { 0.0 } else { sorted_sizes.iter().sum::<f64>() / sorted_sizes.len() as f64 },
+        p50: calculate_percentile(&sorted_sizes, 50.0),
+        p90: calculate_percentile(&sorted_sizes, 90.0),
+        p95: calculate_percentile(&sorted_sizes, 95.0),
+        p99: calculate_percentile(&sorted_sizes, 99.0),
+        max: sorted_sizes.last().copied().unwrap_or(0.0),
+    };
+    
     // Convert status map to sorted vec
     let mut status_codes: Vec<StatusCodeCount> = status_map
         .into_iter()
@@ -1351,12 +3213,18 @@ fn calculate_stats(results: Vec<RequestResult>, num_requests: u32, total_time_se
     });
     
     // Calculate time-series data for charts
-    let throughput_over_time = calculate_throughput_over_time(&results, total_time_secs, &sorted_by_timestamp);
+    let throughput_over_time =
+        calculate_throughput_over_time(&results, total_time_secs, &sorted_by_timestamp, pause_intervals);
     let latency_over_time = calculate_latency_over_time(&results, &sorted_by_timestamp);
+    let window_stats = windows::calculate_window_stats(&results, &sorted_by_timestamp, window_secs);
     let concurrency_over_time = calculate_concurrency_over_time(&results, total_time_secs);
     let request_timeline = calculate_request_timeline(&results);
+    let measured_by_timestamp: Vec<usize> =
+        sorted_by_timestamp.iter().copied().filter(|&i| !results[i].warmup).collect();
+    let (error_groups, ungrouped_errors) = calculate_error_groups(&results, &measured_by_timestamp);
 
     LoadTestStats {
+        run_id: 0,
         total_requests: num_requests,
         successful_requests,
         failed_requests,
@@ -1374,6 +3242,30 @@ fn calculate_stats(results: Vec<RequestResult>, num_requests: u32, total_time_se
         error_logs,
         concurrency_over_time,
         request_timeline,
+        connections_opened: 0,
+        http_version: None,
+        proxy_stats: Vec::new(),
+        auth_stats: None,
+        compression: None,
+        total_bytes_sent,
+        total_bytes_received,
+        sent_mb_per_sec,
+        received_mb_per_sec,
+        response_size_percentiles,
+        capture_stats: None,
+        error_groups,
+        ungrouped_errors,
+        stop_reason: None,
+        cancelled: false,
+        paused_time_secs,
+        pause_intervals: pause_intervals.to_vec(),
+        annotations: Vec::new(),
+        slo_verdict: None,
+        warmup_requests,
+        measurement_start_secs,
+        window_stats,
+        history_id: None,
+        history_error: None,
     }
 }
 
@@ -1393,7 +3285,143 @@ pub fn run() {
         .plugin(tauri_plugin_store::Builder::default().build())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
-        .invoke_handler(tauri::generate_handler![run_load_test, cancel_load_test, get_available_cpus])
+        .invoke_handler(tauri::generate_handler![
+            run_load_test,
+            cancel_load_test,
+            pause_load_test,
+            resume_load_test,
+            update_running_test,
+            cancel_run,
+            list_runs,
+            get_run_status,
+            batch::run_batch,
+            capacity::run_capacity_search,
+            history::list_saved_runs,
+            history::search_saved_runs,
+            history::load_saved_run,
+            history::delete_saved_run,
+            history::tag_saved_run,
+            compare::compare_runs,
+            get_available_cpus
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_error_message_collapses_variable_parts() {
+        assert_eq!(
+            normalize_error_message("error sending request for url (http://127.0.0.1:8080/api?_cb=1700000000123): timed out"),
+            "error sending request for url (<url>): timed out"
+        );
+        assert_eq!(normalize_error_message("connect to 10.0.0.12:54321 failed"), "connect to #.#.#.#:# failed");
+        assert_eq!(normalize_error_message("timed out after 30001ms"), "timed out after #ms");
+        assert_eq!(
+            normalize_error_message("order 550e8400-e29b-41d4-a716-446655440000 not found"),
+            normalize_error_message("order 6ba7b810-9dad-11d1-80b4-00c04fd430c8 not found")
+        );
+        assert_eq!(normalize_error_message("trace [3f9a0c11d2e4] dropped"), "trace [#] dropped");
+    }
+
+    #[test]
+    fn normalize_error_message_keeps_distinct_errors_apart() {
+        let messages = [
+            "Connection refused (os error 111)",
+            "Connection reset by peer (os error 104)",
+            "HTTP 503",
+            "operation timed out",
+            "dns error: failed to lookup address information",
+        ];
+        let normalized: std::collections::HashSet<String> =
+            messages.iter().map(|message| normalize_error_message(message)).collect();
+        assert_eq!(normalized.len(), messages.len());
+        // Words are not mistaken for IDs
+        assert_eq!(normalize_error_message("deadline exceeded"), "deadline exceeded");
+    }
+
+    #[test]
+    fn ip_literal_targets_must_match_the_ip_preference() {
+        let v4: IpAddr = "192.0.2.10".parse().unwrap();
+        let v6: IpAddr = "2001:db8::10".parse().unwrap();
+        assert!(validate_target_address(v4, IpPreference::V4Only, &[]).is_ok());
+        assert!(validate_target_address(v6, IpPreference::PreferV4, &[]).is_ok());
+        assert!(validate_target_address(v6, IpPreference::V4Only, &[]).is_err());
+        assert!(validate_target_address(v4, IpPreference::V6Only, &[]).is_err());
+        // A local source address fixes the family
+        assert!(validate_target_address(v6, IpPreference::Any, &["192.0.2.1".parse().unwrap()]).is_err());
+        assert!(validate_target_address(v6, IpPreference::Any, &["2001:db8::1".parse().unwrap()]).is_ok());
+    }
+
+    #[tokio::test]
+    async fn h2c_probe_connects_like_the_run() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let target = listener.local_addr().unwrap().to_string();
+        let config = |ip_preference: &str, local_addresses: &[&str]| -> LoadTestConfig {
+            serde_json::from_value(serde_json::json!({
+                "url": format!("http://{}/", target),
+                "method": "GET",
+                "num_requests": 1,
+                "concurrency": 1,
+                "use_http2": true,
+                "headers": [],
+                "ip_preference": ip_preference,
+                "local_addresses": local_addresses,
+            }))
+            .unwrap()
+        };
+
+        let stream = connect_probe(&config("V4Only", &["127.0.0.1"]), &target).await.unwrap();
+        assert_eq!(stream.local_addr().unwrap().ip(), "127.0.0.1".parse::<IpAddr>().unwrap());
+        // Neither the IP preference nor a local address of the other family is bypassed
+        assert!(connect_probe(&config("V6Only", &[]), &target).await.is_err());
+        assert!(connect_probe(&config("Any", &["::1"]), &target).await.is_err());
+    }
+
+    fn failed_result(timestamp_ms: f64, body: &str) -> RequestResult {
+        RequestResult {
+            status: 500,
+            duration_ms: 10.0,
+            success: false,
+            error: None,
+            error_type: ErrorType::Response,
+            timestamp_ms,
+            proxy_index: None,
+            body_wire_bytes: 0,
+            body_decoded_bytes: 0,
+            decode_time_ms: None,
+            request_bytes: 0,
+            response_bytes: 0,
+            captured_response: Some(CapturedResponse {
+                headers: vec![CustomHeader { key: "content-type".into(), value: "text/plain".into() }],
+                body: body.into(),
+                body_size: body.len() as u64,
+                truncated: false,
+            }),
+            warmup: false,
+        }
+    }
+
+    #[test]
+    fn capture_copies_count_against_the_budget() {
+        let results = vec![failed_result(10.0, "first"), failed_result(20.0, "second")];
+        let original_bytes: u64 =
+            results.iter().filter_map(|result| result.captured_response.as_ref()).map(captured_size).sum();
+        let mut stats = calculate_stats(results, 2, 1.0, &[], 1.0);
+        assert_eq!(stats.error_logs.len(), 2);
+        assert_eq!(stats.error_groups[0].examples.len(), 2);
+
+        // Room for the originals and both error log copies, but not for the group examples
+        let budget = original_bytes * 2;
+        let mut capture_stats = CaptureStats { captured: 2, dropped_over_budget: 0, bytes_used: original_bytes };
+        charge_capture_copies(&mut stats, budget, &mut capture_stats);
+        assert_eq!(capture_stats.bytes_used, budget);
+        assert_eq!(capture_stats.dropped_over_budget, 2);
+        assert!(stats.error_logs.iter().all(|entry| entry.response.is_some()));
+        assert!(stats.error_groups[0].examples.iter().all(|entry| entry.response.is_none()));
+        assert!(stats.results.iter().all(|result| result.captured_response.is_some()));
+    }
+}
@@ -0,0 +1,74 @@
+//! Think time between iterations of a virtual user.
+//!
+//! Each worker sleeps a sampled think time after a response before sending its next request,
+//! modelling a user reading a page. Pacing (a fixed interval between iteration starts) is
+//! applied alongside it in `run_worker`.
+
+use crate::LoadTestError;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Longest think time value accepted, in milliseconds (1 hour)
+const MAX_THINK_TIME_MS: f64 = 3_600_000.0;
+/// Longest pacing interval accepted, in seconds (1 hour)
+pub(crate) const MAX_PACING_SECS: f64 = 3_600.0;
+
+/// Distribution the think time is drawn from, in milliseconds
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(tag = "type")]
+pub enum ThinkTime {
+    /// No think time
+    #[default]
+    None,
+    Constant { ms: f64 },
+    Uniform { min_ms: f64, max_ms: f64 },
+    /// Normal distribution, negative samples clamped to 0
+    Normal { mean_ms: f64, std_dev_ms: f64 },
+    Exponential { mean_ms: f64 },
+}
+
+impl ThinkTime {
+    pub(crate) fn validate(&self) -> Result<(), LoadTestError> {
+        let valid = |value: f64| value.is_finite() && (0.0..=MAX_THINK_TIME_MS).contains(&value);
+        let ok = match *self {
+            ThinkTime::None => true,
+            ThinkTime::Constant { ms } => valid(ms),
+            ThinkTime::Uniform { min_ms, max_ms } => valid(min_ms) && valid(max_ms) && min_ms <= max_ms,
+            ThinkTime::Normal { mean_ms, std_dev_ms } => valid(mean_ms) && valid(std_dev_ms),
+            ThinkTime::Exponential { mean_ms } => valid(mean_ms),
+        };
+        if ok {
+            Ok(())
+        } else {
+            Err(LoadTestError::InvalidConfig(
+                format!("Invalid think time {:?}: values must be between 0 and 3600000 ms (min <= max)", self)
+            ))
+        }
+    }
+
+    /// Draws the next think time (None when disabled or zero)
+    pub(crate) fn sample(&self) -> Option<Duration> {
+        let mut rng = rand::thread_rng();
+        let ms = match *self {
+            ThinkTime::None => return None,
+            ThinkTime::Constant { ms } => ms,
+            ThinkTime::Uniform { min_ms, max_ms } => {
+                if max_ms > min_ms {
+                    rng.gen_range(min_ms..max_ms)
+                } else {
+                    min_ms
+                }
+            }
+            ThinkTime::Normal { mean_ms, std_dev_ms } => {
+                // Box-Muller transform; 1 - u keeps the logarithm finite
+                let u1: f64 = 1.0 - rng.gen::<f64>();
+                let u2: f64 = rng.gen();
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                mean_ms + std_dev_ms * z
+            }
+            ThinkTime::Exponential { mean_ms } => -mean_ms * (1.0 - rng.gen::<f64>()).ln(),
+        };
+        Duration::try_from_secs_f64(ms / 1000.0).ok().filter(|think_time| !think_time.is_zero())
+    }
+}
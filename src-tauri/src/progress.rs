@@ -0,0 +1,119 @@
+//! Live progress aggregated in the collector.
+//!
+//! The collector feeds every result to a `ProgressAggregator` and emits a `ProgressUpdate`
+//! on a fixed interval, so workers only bump counters. Rates and percentiles cover a sliding
+//! window of recent completions rather than the whole run, so the live view tracks changes.
+
+use crate::{ProgressUpdate, RequestResult, RunId, StatusCodeCount, TestCounters};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::Ordering;
+
+/// Length of the sliding window for live rates and percentiles
+const PROGRESS_WINDOW_SECS: f64 = 5.0;
+
+/// A completion kept in the sliding window
+struct WindowEntry {
+    completed_secs: f64,
+    duration_ms: f64,
+    success: bool,
+}
+
+pub(crate) struct ProgressAggregator {
+    window: VecDeque<WindowEntry>,
+    window_failures: u32,
+    status_counts: BTreeMap<u16, u32>,
+    latest_response_time_ms: f64,
+    /// Scratch buffer for percentile selection, reused between updates
+    durations: Vec<f64>,
+}
+
+impl ProgressAggregator {
+    pub(crate) fn new() -> Self {
+        Self {
+            window: VecDeque::new(),
+            window_failures: 0,
+            status_counts: BTreeMap::new(),
+            latest_response_time_ms: 0.0,
+            durations: Vec::new(),
+        }
+    }
+
+    pub(crate) fn observe(&mut self, result: &RequestResult) {
+        self.window.push_back(WindowEntry {
+            completed_secs: result.timestamp_ms / 1000.0,
+            duration_ms: result.duration_ms,
+            success: result.success,
+        });
+        if !result.success {
+            self.window_failures += 1;
+        }
+        *self.status_counts.entry(result.status).or_insert(0) += 1;
+        self.latest_response_time_ms = result.duration_ms;
+    }
+
+    /// Builds the progress update at `elapsed_secs` since test start. `active_secs` excludes
+    /// paused time and drives the cumulative rate.
+    pub(crate) fn update(
+        &mut self,
+        run_id: RunId,
+        total: u32,
+        counters: &TestCounters,
+        elapsed_secs: f64,
+        active_secs: f64,
+    ) -> ProgressUpdate {
+        // Slide the window forward
+        let window_start = elapsed_secs - PROGRESS_WINDOW_SECS;
+        while self.window.front().is_some_and(|entry| entry.completed_secs < window_start) {
+            if let Some(entry) = self.window.pop_front() {
+                if !entry.success {
+                    self.window_failures -= 1;
+                }
+            }
+        }
+
+        let window_secs = PROGRESS_WINDOW_SECS.min(elapsed_secs);
+        let window_requests = self.window.len();
+        self.durations.clear();
+        self.durations.extend(self.window.iter().map(|entry| entry.duration_ms));
+
+        let completed = counters.completed.load(Ordering::Relaxed);
+        let mut status_codes: Vec<StatusCodeCount> = self
+            .status_counts
+            .iter()
+            .map(|(&code, &count)| StatusCodeCount { code, count })
+            .collect();
+        status_codes.sort_by_key(|status| std::cmp::Reverse(status.count));
+
+        ProgressUpdate {
+            run_id,
+            completed,
+            total,
+            successful: counters.successful.load(Ordering::Relaxed),
+            failed: counters.failed.load(Ordering::Relaxed),
+            current_rps: if active_secs > 0.0 { completed as f64 / active_secs } else { 0.0 },
+            elapsed_secs,
+            latest_response_time_ms: self.latest_response_time_ms,
+            window_secs,
+            window_rps: if window_secs > 0.0 { window_requests as f64 / window_secs } else { 0.0 },
+            window_p50_ms: select_percentile(&mut self.durations, 50.0),
+            window_p95_ms: select_percentile(&mut self.durations, 95.0),
+            window_p99_ms: select_percentile(&mut self.durations, 99.0),
+            window_error_rate_percent: if window_requests == 0 {
+                0.0
+            } else {
+                self.window_failures as f64 * 100.0 / window_requests as f64
+            },
+            in_flight: counters.in_flight.load(Ordering::Relaxed),
+            status_codes,
+        }
+    }
+}
+
+/// Percentile of unsorted values in linear time (same rank as `calculate_percentile`)
+fn select_percentile(values: &mut [f64], percentile: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let index = (((percentile / 100.0) * (values.len() - 1) as f64).round() as usize).min(values.len() - 1);
+    *values.select_nth_unstable_by(index, |a, b| a.total_cmp(b)).1
+}
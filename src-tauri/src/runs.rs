@@ -0,0 +1,408 @@
+//! Registry of active test runs.
+//!
+//! Every `run_load_test` call registers a `RunHandle` holding the run's cancellation, drain
+//! and pause state, its counters and live settings, so several tests can run side by
+//! side and be controlled individually by `RunId`. The handle is removed when the run ends.
+
+use crate::{Annotation, PauseInterval, TestCounters};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::Notify;
+
+/// Identifier of a test run, unique for the lifetime of the app
+pub type RunId = u64;
+
+static NEXT_RUN_ID: AtomicU64 = AtomicU64::new(1);
+static RUNS: Mutex<BTreeMap<RunId, Arc<RunHandle>>> = Mutex::new(BTreeMap::new());
+
+/// Bumped by `cancel_all`, so sequences of runs (batches, capacity searches) notice a cancel
+/// that arrives between two runs, when there is no run to cancel
+static CANCEL_EPOCH: AtomicU64 = AtomicU64::new(0);
+static CANCEL_NOTIFY: Notify = Notify::const_new();
+
+/// Lifecycle state of an active run
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum RunState {
+    /// Preparing clients, credentials and payloads; no requests sent yet
+    Starting,
+    Running,
+    Paused,
+    /// Cancelled (or auto-stopped); waiting for workers to finish
+    Stopping,
+}
+
+/// Snapshot of an active run for `list_runs` / `get_run_status`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RunStatus {
+    pub run_id: RunId,
+    pub url: String,
+    pub state: RunState,
+    /// Unix time the run was started, in milliseconds
+    pub started_at_ms: u64,
+    /// Time since dispatch began, excluding pauses, as in the final stats (0 while starting)
+    pub elapsed_secs: f64,
+    pub total: u32,
+    pub completed: u32,
+    pub successful: u32,
+    pub failed: u32,
+    pub concurrency: u32,
+    /// Rate limit per worker (0 = unlimited)
+    pub rate_limit: f64,
+}
+
+/// Payload of run lifecycle events, tagged with the run they belong to
+#[derive(Debug, Serialize, Clone)]
+pub struct RunEvent<T: Serialize + Clone> {
+    pub run_id: RunId,
+    pub payload: T,
+}
+
+/// Payload of the `load-test-started` event
+#[derive(Debug, Serialize, Clone)]
+pub struct RunStarted {
+    pub url: String,
+    /// The run config's `client_token`
+    pub client_token: Option<String>,
+}
+
+/// Pause and resume times recorded by the pause commands
+struct PauseLog {
+    paused_since: Option<Instant>,
+    intervals: Vec<(Instant, Instant)>,
+}
+
+impl PauseLog {
+    /// Total paused time up to `now`, including a pause still in progress
+    fn paused_duration(&self, now: Instant) -> Duration {
+        let closed: Duration = self.intervals.iter().map(|(start, end)| end.saturating_duration_since(*start)).sum();
+        closed + self.paused_since.map_or(Duration::ZERO, |since| now.saturating_duration_since(since))
+    }
+
+    /// Pause intervals relative to `start`, closing a pause still in progress at `end`
+    fn intervals_since(&self, start: Instant, end: Instant) -> Vec<PauseInterval> {
+        self.intervals
+            .iter()
+            .copied()
+            .chain(self.paused_since.map(|since| (since, end)))
+            .map(|(from, to)| PauseInterval {
+                start_secs: from.saturating_duration_since(start).as_secs_f64(),
+                end_secs: to.saturating_duration_since(start).as_secs_f64(),
+            })
+            .collect()
+    }
+}
+
+/// Settings that `update_running_test` can change while the test runs
+pub(crate) struct RunControl {
+    /// Number of workers that should be active
+    pub(crate) concurrency: AtomicU32,
+    /// Minimum interval between requests per worker in nanoseconds (0 = no rate limit)
+    rate_limit_interval_ns: AtomicU64,
+    pub(crate) annotations: Mutex<Vec<Annotation>>,
+}
+
+impl RunControl {
+    fn new(concurrency: u32, rate_limit: f64) -> Self {
+        let control = Self {
+            concurrency: AtomicU32::new(concurrency),
+            rate_limit_interval_ns: AtomicU64::new(0),
+            annotations: Mutex::new(Vec::new()),
+        };
+        control.set_rate_limit(rate_limit);
+        control
+    }
+
+    /// Sets the per-worker rate limit in requests per second (0 = unlimited)
+    pub(crate) fn set_rate_limit(&self, rate_limit: f64) {
+        let interval_ns = if rate_limit > 0.0 {
+            Duration::try_from_secs_f64(1.0 / rate_limit)
+                .map_or(u64::MAX, |interval| u64::try_from(interval.as_nanos()).unwrap_or(u64::MAX))
+        } else {
+            0
+        };
+        self.rate_limit_interval_ns.store(interval_ns, Ordering::Relaxed);
+    }
+
+    pub(crate) fn rate_limit(&self) -> f64 {
+        match self.rate_limit_interval_ns.load(Ordering::Relaxed) {
+            0 => 0.0,
+            interval_ns => 1e9 / interval_ns as f64,
+        }
+    }
+
+    pub(crate) fn rate_limit_interval(&self) -> Option<Duration> {
+        match self.rate_limit_interval_ns.load(Ordering::Relaxed) {
+            0 => None,
+            interval_ns => Some(Duration::from_nanos(interval_ns)),
+        }
+    }
+}
+
+/// State of one active run, shared by its workers and the control commands
+pub(crate) struct RunHandle {
+    pub(crate) id: RunId,
+    url: String,
+    started_at_ms: u64,
+    created: Instant,
+    /// Time zero of the run's timestamps, set when dispatch begins
+    start: OnceLock<Instant>,
+    cancelled: AtomicBool,
+    /// Draining cancel: no new requests are dispatched, in-flight ones complete
+    draining: AtomicBool,
+    paused: AtomicBool,
+    pause_log: Mutex<PauseLog>,
+    total: u32,
+    pub(crate) counters: Arc<TestCounters>,
+    pub(crate) control: RunControl,
+}
+
+impl RunHandle {
+    #[inline]
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Whether the run should stop dispatching new requests: cancelled outright, or
+    /// cancelled with draining, where in-flight requests still run to completion
+    #[inline]
+    pub(crate) fn is_stopping(&self) -> bool {
+        self.is_cancelled() || self.draining.load(Ordering::SeqCst)
+    }
+
+    #[inline]
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Stops the run; with `drain` only dispatch stops and in-flight requests complete
+    pub(crate) fn cancel(&self, drain: bool) {
+        if drain {
+            self.draining.store(true, Ordering::SeqCst);
+        } else {
+            self.cancelled.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Pauses dispatch. Returns false if already paused.
+    pub(crate) fn pause(&self) -> bool {
+        let Ok(mut log) = self.pause_log.lock() else {
+            return false;
+        };
+        if log.paused_since.is_some() {
+            return false;
+        }
+        log.paused_since = Some(Instant::now());
+        self.paused.store(true, Ordering::SeqCst);
+        true
+    }
+
+    /// Resumes dispatch. Returns false if not paused.
+    pub(crate) fn resume(&self) -> bool {
+        let Ok(mut log) = self.pause_log.lock() else {
+            return false;
+        };
+        let Some(since) = log.paused_since.take() else {
+            return false;
+        };
+        log.intervals.push((since, Instant::now()));
+        self.paused.store(false, Ordering::SeqCst);
+        true
+    }
+
+    /// Sets the number of workers, capped at the run's request count. Returns the value set.
+    pub(crate) fn set_concurrency(&self, concurrency: u32) -> u32 {
+        let concurrency = concurrency.min(self.total.max(1));
+        self.control.concurrency.store(concurrency, Ordering::Relaxed);
+        concurrency
+    }
+
+    /// Total paused time so far
+    pub(crate) fn paused_duration(&self) -> Duration {
+        self.pause_log.lock().map_or(Duration::ZERO, |log| log.paused_duration(Instant::now()))
+    }
+
+    /// Time since dispatch began, excluding pauses; zero before dispatch
+    pub(crate) fn active_duration(&self) -> Duration {
+        self.start.get().map_or(Duration::ZERO, |start| start.elapsed().saturating_sub(self.paused_duration()))
+    }
+
+    /// Pause intervals relative to the run start, closing a pause in progress at `end`
+    pub(crate) fn pause_intervals(&self, end: Instant) -> Vec<PauseInterval> {
+        self.pause_log.lock().map_or_else(|_| Vec::new(), |log| log.intervals_since(self.start_time(), end))
+    }
+
+    /// Marks the start of dispatch; timestamps and annotations are relative to it
+    pub(crate) fn mark_started(&self, start: Instant) {
+        let _ = self.start.set(start);
+    }
+
+    /// Wall clock time the run was registered, in Unix milliseconds
+    pub(crate) fn started_at_ms(&self) -> u64 {
+        self.started_at_ms
+    }
+
+    /// Time zero of the run (creation time until dispatch begins)
+    pub(crate) fn start_time(&self) -> Instant {
+        self.start.get().copied().unwrap_or(self.created)
+    }
+
+    pub(crate) fn status(&self) -> RunStatus {
+        let state = if self.is_stopping() {
+            RunState::Stopping
+        } else if self.start.get().is_none() {
+            RunState::Starting
+        } else if self.is_paused() {
+            RunState::Paused
+        } else {
+            RunState::Running
+        };
+        RunStatus {
+            run_id: self.id,
+            url: self.url.clone(),
+            state,
+            started_at_ms: self.started_at_ms,
+            elapsed_secs: self.active_duration().as_secs_f64(),
+            total: self.total,
+            completed: self.counters.completed.load(Ordering::Relaxed),
+            successful: self.counters.successful.load(Ordering::Relaxed),
+            failed: self.counters.failed.load(Ordering::Relaxed),
+            concurrency: self.control.concurrency.load(Ordering::Relaxed),
+            rate_limit: self.control.rate_limit(),
+        }
+    }
+}
+
+/// Removes the run from the registry when dropped, however the run ends
+pub(crate) struct RunGuard(pub(crate) Arc<RunHandle>);
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        if let Ok(mut runs) = RUNS.lock() {
+            runs.remove(&self.0.id);
+        }
+    }
+}
+
+/// Registers a new run and returns its guard
+pub(crate) fn register(url: String, total: u32, concurrency: u32, rate_limit: f64) -> RunGuard {
+    let handle = Arc::new(RunHandle {
+        id: NEXT_RUN_ID.fetch_add(1, Ordering::Relaxed),
+        url,
+        started_at_ms: unix_millis(),
+        created: Instant::now(),
+        start: OnceLock::new(),
+        cancelled: AtomicBool::new(false),
+        draining: AtomicBool::new(false),
+        paused: AtomicBool::new(false),
+        pause_log: Mutex::new(PauseLog { paused_since: None, intervals: Vec::new() }),
+        total,
+        counters: Arc::new(TestCounters::new()),
+        control: RunControl::new(concurrency, rate_limit),
+    });
+    if let Ok(mut runs) = RUNS.lock() {
+        runs.insert(handle.id, Arc::clone(&handle));
+    }
+    RunGuard(handle)
+}
+
+/// Looks up an active run
+pub(crate) fn get(run_id: RunId) -> Option<Arc<RunHandle>> {
+    RUNS.lock().ok()?.get(&run_id).cloned()
+}
+
+/// All active runs, oldest first
+pub(crate) fn all() -> Vec<Arc<RunHandle>> {
+    RUNS.lock().map(|runs| runs.values().cloned().collect()).unwrap_or_default()
+}
+
+/// Cancels every active run, and any batch or capacity search between runs. Returns the
+/// cancelled runs.
+pub(crate) fn cancel_all(drain: bool) -> Vec<Arc<RunHandle>> {
+    let runs = all();
+    for run in &runs {
+        run.cancel(drain);
+    }
+    CANCEL_EPOCH.fetch_add(1, Ordering::SeqCst);
+    CANCEL_NOTIFY.notify_waiters();
+    runs
+}
+
+/// Marker taken when a sequence of runs starts, for `cancelled_since` and `cooldown`
+pub(crate) fn cancel_epoch() -> u64 {
+    CANCEL_EPOCH.load(Ordering::SeqCst)
+}
+
+/// Whether `cancel_all` was called after `epoch` was taken
+pub(crate) fn cancelled_since(epoch: u64) -> bool {
+    CANCEL_EPOCH.load(Ordering::SeqCst) != epoch
+}
+
+/// Waits between two runs of a sequence. Returns false, as soon as it happens, when
+/// `cancel_all` is called (or was called after `epoch` was taken).
+pub(crate) async fn cooldown(duration: Duration, epoch: u64) -> bool {
+    let notified = CANCEL_NOTIFY.notified();
+    tokio::pin!(notified);
+    // Register before checking the epoch so a cancel in between is not missed
+    notified.as_mut().enable();
+    if cancelled_since(epoch) {
+        return false;
+    }
+    tokio::select! {
+        _ = tokio::time::sleep(duration) => true,
+        _ = notified => false,
+    }
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn cooldown_ends_on_cancel_all() {
+        let epoch = cancel_epoch();
+        assert!(cooldown(Duration::from_millis(1), epoch).await);
+
+        let waiting = tokio::spawn(cooldown(Duration::from_secs(3600), epoch));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        cancel_all(false);
+        let settled = tokio::time::timeout(Duration::from_secs(5), waiting).await.unwrap().unwrap();
+        assert!(!settled);
+
+        // A cancel before the cooldown starts is not missed
+        assert!(cancelled_since(epoch));
+        assert!(!cooldown(Duration::from_secs(3600), epoch).await);
+        assert!(cooldown(Duration::ZERO, cancel_epoch()).await);
+    }
+
+    #[test]
+    fn elapsed_time_excludes_setup_and_pauses() {
+        let guard = register("http://localhost".into(), 10, 2, 0.0);
+        let run = &guard.0;
+        assert_eq!(run.status().elapsed_secs, 0.0);
+
+        let now = Instant::now();
+        run.mark_started(now - Duration::from_secs(10));
+        run.pause_log.lock().unwrap().intervals.push((now - Duration::from_secs(8), now - Duration::from_secs(5)));
+        let elapsed = run.status().elapsed_secs;
+        assert!((7.0..8.0).contains(&elapsed), "elapsed {}", elapsed);
+    }
+
+    #[test]
+    fn concurrency_updates_are_capped_at_the_request_count() {
+        let guard = register("http://localhost".into(), 10, 2, 0.0);
+        let run = &guard.0;
+        assert_eq!(run.set_concurrency(4), 4);
+        assert_eq!(run.set_concurrency(u32::MAX), 10);
+        assert_eq!(run.status().concurrency, 10);
+    }
+}
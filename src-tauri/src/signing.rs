@@ -0,0 +1,556 @@
+//! Request signing: generic HMAC over a configurable canonical string, and AWS SigV4.
+//!
+//! Signers run in `make_request` on the fully built request, after authentication, so the
+//! signature covers the final URL (cache buster included), headers and body.
+
+use crate::LoadTestError;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use reqwest::header::{HeaderName, HeaderValue, AUTHORIZATION};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default HMAC canonical string: method, path with query, timestamp and body hash
+const DEFAULT_HMAC_TEMPLATE: &str = "{method}\n{path}\n{query}\n{timestamp}\n{body_sha256}";
+
+/// Characters AWS leaves unencoded (RFC 3986 unreserved)
+const AWS_UNRESERVED: &percent_encoding::AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// How requests are signed
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum SigningConfig {
+    /// HMAC over a canonical string built from a template
+    Hmac {
+        secret: String,
+        #[serde(default)]
+        algorithm: HmacAlgorithm,
+        /// String to sign. Placeholders: {method}, {path}, {query}, {host}, {url},
+        /// {timestamp} (unix seconds), {timestamp_ms}, {body}, {body_sha256}
+        #[serde(default = "default_hmac_template")]
+        canonical_template: String,
+        /// Header that receives the signature
+        #[serde(default = "default_signature_header")]
+        signature_header: String,
+        /// Prefix prepended to the signature value (e.g. "sha256=")
+        #[serde(default)]
+        signature_prefix: String,
+        #[serde(default)]
+        encoding: SignatureEncoding,
+        /// Header that receives the timestamp used in the signature, if any
+        #[serde(default)]
+        timestamp_header: Option<String>,
+    },
+    /// AWS Signature Version 4 with static credentials
+    AwsSigV4 {
+        access_key_id: String,
+        secret_access_key: String,
+        #[serde(default)]
+        session_token: Option<String>,
+        region: String,
+        service: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum HmacAlgorithm {
+    #[default]
+    Sha256,
+    Sha512,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum SignatureEncoding {
+    #[default]
+    Hex,
+    Base64,
+}
+
+fn default_hmac_template() -> String {
+    DEFAULT_HMAC_TEMPLATE.to_string()
+}
+
+fn default_signature_header() -> String {
+    "X-Signature".to_string()
+}
+
+/// Signs a fully built request in place
+pub(crate) trait RequestSigner: Send + Sync {
+    fn sign(&self, request: &mut reqwest::Request) -> Result<(), String>;
+}
+
+/// Creates the signer for a signing configuration
+pub(crate) fn build_signer(config: SigningConfig) -> Result<Box<dyn RequestSigner>, LoadTestError> {
+    match config {
+        SigningConfig::Hmac {
+            secret,
+            algorithm,
+            canonical_template,
+            signature_header,
+            signature_prefix,
+            encoding,
+            timestamp_header,
+        } => {
+            let header_name = |name: &str| {
+                HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|_| LoadTestError::InvalidConfig(format!("Invalid signature header name '{}'", name)))
+            };
+            Ok(Box::new(HmacSigner {
+                secret: secret.into_bytes(),
+                algorithm,
+                canonical_template,
+                signature_header: header_name(&signature_header)?,
+                signature_prefix,
+                encoding,
+                timestamp_header: timestamp_header.as_deref().map(header_name).transpose()?,
+            }))
+        }
+        SigningConfig::AwsSigV4 { access_key_id, secret_access_key, session_token, region, service } => {
+            if access_key_id.is_empty() || secret_access_key.is_empty() || region.is_empty() || service.is_empty() {
+                return Err(LoadTestError::InvalidConfig(
+                    "AWS SigV4 requires access key, secret key, region and service".into(),
+                ));
+            }
+            Ok(Box::new(SigV4Signer {
+                access_key_id,
+                secret_access_key,
+                session_token: session_token.filter(|token| !token.is_empty()),
+                region,
+                service,
+            }))
+        }
+    }
+}
+
+/// Returns the in-memory request body (empty if none)
+fn body_bytes(request: &reqwest::Request) -> Result<&[u8], String> {
+    match request.body() {
+        Some(body) => body.as_bytes().ok_or_else(|| "streaming bodies cannot be signed".to_string()),
+        None => Ok(&[]),
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+struct HmacSigner {
+    secret: Vec<u8>,
+    algorithm: HmacAlgorithm,
+    canonical_template: String,
+    signature_header: HeaderName,
+    signature_prefix: String,
+    encoding: SignatureEncoding,
+    timestamp_header: Option<HeaderName>,
+}
+
+impl HmacSigner {
+    /// Replaces known `{placeholder}`s in a single pass; unknown ones are kept as is
+    fn canonical_string(&self, request: &reqwest::Request, timestamp_ms: u128) -> Result<String, String> {
+        let url = request.url();
+        let body = body_bytes(request)?;
+        let mut output = String::with_capacity(self.canonical_template.len() + 64);
+        let mut rest = self.canonical_template.as_str();
+
+        while let Some(open) = rest.find('{') {
+            output.push_str(&rest[..open]);
+            let Some(close) = rest[open..].find('}').map(|i| open + i) else {
+                break;
+            };
+            let value = match &rest[open + 1..close] {
+                "method" => request.method().to_string(),
+                "path" => url.path().to_string(),
+                "query" => url.query().unwrap_or_default().to_string(),
+                "host" => url.host_str().unwrap_or_default().to_string(),
+                "url" => url.to_string(),
+                "timestamp" => (timestamp_ms / 1000).to_string(),
+                "timestamp_ms" => timestamp_ms.to_string(),
+                "body" => String::from_utf8_lossy(body).into_owned(),
+                "body_sha256" => hex(&Sha256::digest(body)),
+                _ => rest[open..=close].to_string(),
+            };
+            output.push_str(&value);
+            rest = &rest[close + 1..];
+        }
+        output.push_str(rest);
+        Ok(output)
+    }
+
+    fn sign_at(&self, request: &mut reqwest::Request, timestamp_ms: u128) -> Result<(), String> {
+        let canonical = self.canonical_string(request, timestamp_ms)?;
+
+        let signature = match self.algorithm {
+            HmacAlgorithm::Sha256 => hmac_sha256(&self.secret, canonical.as_bytes()),
+            HmacAlgorithm::Sha512 => {
+                let mut mac = Hmac::<Sha512>::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+                mac.update(canonical.as_bytes());
+                mac.finalize().into_bytes().to_vec()
+            }
+        };
+        let encoded = match self.encoding {
+            SignatureEncoding::Hex => hex(&signature),
+            SignatureEncoding::Base64 => base64::engine::general_purpose::STANDARD.encode(&signature),
+        };
+
+        let value = HeaderValue::from_str(&format!("{}{}", self.signature_prefix, encoded))
+            .map_err(|_| "signature prefix contains characters not allowed in a header".to_string())?;
+        request.headers_mut().insert(self.signature_header.clone(), value);
+        if let Some(timestamp_header) = &self.timestamp_header {
+            request.headers_mut().insert(timestamp_header.clone(), HeaderValue::from((timestamp_ms / 1000) as u64));
+        }
+        Ok(())
+    }
+}
+
+impl RequestSigner for HmacSigner {
+    fn sign(&self, request: &mut reqwest::Request) -> Result<(), String> {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+        self.sign_at(request, timestamp_ms)
+    }
+}
+
+struct SigV4Signer {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+    region: String,
+    service: String,
+}
+
+impl SigV4Signer {
+    /// Canonical URI: every service except S3 encodes each path segment as sent (already
+    /// percent-encoded once by the URL), S3 encodes the decoded segment exactly once
+    fn canonical_uri(&self, url: &reqwest::Url) -> String {
+        let encode = |segment: &str| percent_encoding::utf8_percent_encode(segment, AWS_UNRESERVED).to_string();
+        let uri = url
+            .path()
+            .split('/')
+            .map(|segment| {
+                if self.service == "s3" {
+                    encode(&percent_encoding::percent_decode_str(segment).decode_utf8_lossy())
+                } else {
+                    encode(segment)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        if uri.is_empty() {
+            "/".to_string()
+        } else {
+            uri
+        }
+    }
+}
+
+/// Canonical query string: encoded pairs sorted by key, then value
+fn canonical_query(url: &reqwest::Url) -> String {
+    let encode = |value: &str| percent_encoding::utf8_percent_encode(value, AWS_UNRESERVED).to_string();
+    let mut pairs: Vec<(String, String)> = url.query_pairs().map(|(k, v)| (encode(&k), encode(&v))).collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+impl SigV4Signer {
+    fn sign_at(&self, request: &mut reqwest::Request, unix_secs: u64) -> Result<(), String> {
+        let (date, amz_date) = utc_timestamp(unix_secs);
+        let payload_hash = hex(&Sha256::digest(body_bytes(request)?));
+
+        let url = request.url().clone();
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+
+        let headers = request.headers_mut();
+        headers.insert("x-amz-date", HeaderValue::from_str(&amz_date).map_err(|e| e.to_string())?);
+        // Only S3 requires the payload hash header; other services sign it when present
+        if self.service == "s3" {
+            headers.insert("x-amz-content-sha256", HeaderValue::from_str(&payload_hash).map_err(|e| e.to_string())?);
+        }
+        if let Some(token) = &self.session_token {
+            headers.insert(
+                "x-amz-security-token",
+                HeaderValue::from_str(token).map_err(|_| "invalid session token".to_string())?,
+            );
+        }
+
+        // Sign host, content-type and all x-amz-* headers (reqwest adds Host itself at send time)
+        let mut signed: Vec<(String, String)> = vec![("host".to_string(), host)];
+        for (name, value) in headers.iter() {
+            let name = name.as_str();
+            if name == "content-type" || name.starts_with("x-amz-") {
+                let value = value.to_str().map_err(|e| e.to_string())?;
+                signed.push((name.to_string(), value.split_whitespace().collect::<Vec<_>>().join(" ")));
+            }
+        }
+        signed.sort();
+        let canonical_headers: String = signed.iter().map(|(name, value)| format!("{}:{}\n", name, value)).collect();
+        let signed_headers = signed.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>().join(";");
+
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            request.method(),
+            self.canonical_uri(&url),
+            canonical_query(&url),
+            canonical_headers,
+            signed_headers,
+            payload_hash
+        );
+        let scope = format!("{}/{}/{}/aws4_request", date, self.region, self.service);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let signing_key = [self.region.as_str(), self.service.as_str(), "aws4_request"]
+            .iter()
+            .fold(
+                hmac_sha256(format!("AWS4{}", self.secret_access_key).as_bytes(), date.as_bytes()),
+                |key, part| hmac_sha256(&key, part.as_bytes()),
+            );
+        let signature = hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key_id, scope, signed_headers, signature
+        );
+        request.headers_mut().insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&authorization).map_err(|_| "invalid access key id".to_string())?,
+        );
+        Ok(())
+    }
+}
+
+impl RequestSigner for SigV4Signer {
+    fn sign(&self, request: &mut reqwest::Request) -> Result<(), String> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.sign_at(request, now)
+    }
+}
+
+/// Formats unix seconds as the SigV4 date ("YYYYMMDD") and timestamp ("YYYYMMDDTHHMMSSZ")
+fn utc_timestamp(unix_secs: u64) -> (String, String) {
+    let days = (unix_secs / 86_400) as i64;
+    let secs_of_day = unix_secs % 86_400;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    let date = format!("{:04}{:02}{:02}", year, month, day);
+    let timestamp = format!(
+        "{}T{:02}{:02}{:02}Z",
+        date,
+        secs_of_day / 3_600,
+        (secs_of_day % 3_600) / 60,
+        secs_of_day % 60
+    );
+    (date, timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::{Method, Url};
+
+    /// Unix time of the AWS SigV4 test suite requests (2015-08-30T12:36:00Z)
+    const SUITE_TIME: u64 = 1_440_938_160;
+
+    fn suite_signer(service: &str, session_token: Option<&str>) -> SigV4Signer {
+        SigV4Signer {
+            access_key_id: "AKIDEXAMPLE".into(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".into(),
+            session_token: session_token.map(str::to_string),
+            region: "us-east-1".into(),
+            service: service.into(),
+        }
+    }
+
+    fn signed_authorization(signer: &SigV4Signer, url: &str) -> String {
+        let mut request = reqwest::Request::new(Method::GET, Url::parse(url).unwrap());
+        signer.sign_at(&mut request, SUITE_TIME).unwrap();
+        assert_eq!(request.headers()["x-amz-date"], "20150830T123600Z");
+        request.headers()[AUTHORIZATION].to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn sigv4_get_vanilla() {
+        assert_eq!(
+            signed_authorization(&suite_signer("service", None), "https://example.amazonaws.com/"),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    #[test]
+    fn sigv4_get_vanilla_query_order_key_case() {
+        assert_eq!(
+            signed_authorization(
+                &suite_signer("service", None),
+                "https://example.amazonaws.com/?Param2=value2&Param1=value1"
+            ),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500"
+        );
+    }
+
+    #[test]
+    fn sigv4_get_vanilla_query_order_encoded() {
+        let url = Url::parse("https://example.amazonaws.com/?Param-3=Value3&Param=Value2&%E1%88%B4=Value1").unwrap();
+        assert_eq!(canonical_query(&url), "%E1%88%B4=Value1&Param=Value2&Param-3=Value3");
+        assert!(signed_authorization(&suite_signer("service", None), url.as_str())
+            .ends_with("Signature=371d3713e185cc334048618a97f809c9ffe339c62934c032af5a0e595648fcac"));
+    }
+
+    #[test]
+    fn sigv4_get_vanilla_with_session_token() {
+        let signer = suite_signer("service", Some("6e86291e8372ff2a2260956d9b8aae1d763fbf315fa00fa31553b73ebf194267"));
+        assert_eq!(
+            signed_authorization(&signer, "https://example.amazonaws.com/"),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date;x-amz-security-token, \
+             Signature=07ec1639c89043aa0e3e2de82b96708f198cceab042d4a97044c66dd9f74e7f8"
+        );
+    }
+
+    #[test]
+    fn sigv4_canonical_uri_encodes_path_segments() {
+        let signer = suite_signer("service", None);
+        let uri = |path: &str| {
+            signer.canonical_uri(&Url::parse(&format!("https://example.amazonaws.com{}", path)).unwrap())
+        };
+
+        assert_eq!(uri(""), "/");
+        // double-url-encode and double-encode-path from the test suite
+        assert_eq!(
+            uri("/functions/arn%3Aaws%3Alambda%3Aus-west-2%3A892717189312%3Afunction%3Amy-rusty-fun/invocations"),
+            "/functions/arn%253Aaws%253Alambda%253Aus-west-2%253A892717189312%253Afunction%253Amy-rusty-fun/invocations"
+        );
+        assert_eq!(uri("/test/@connections/JBDvjfGEIAMCERw%3D"), "/test/%40connections/JBDvjfGEIAMCERw%253D");
+        assert_eq!(uri("/example space/ሴ"), "/example%2520space/%25E1%2588%25B4");
+    }
+
+    #[test]
+    fn sigv4_canonical_uri_encodes_s3_paths_once() {
+        let signer = suite_signer("s3", None);
+        let uri = |path: &str| {
+            signer.canonical_uri(&Url::parse(&format!("https://bucket.s3.amazonaws.com{}", path)).unwrap())
+        };
+
+        assert_eq!(uri("/example space/ሴ"), "/example%20space/%E1%88%B4");
+        assert_eq!(uri("/photos/my%20file:1.jpg"), "/photos/my%20file%3A1.jpg");
+    }
+
+    #[test]
+    fn sigv4_adds_payload_hash_for_s3_only() {
+        let mut request =
+            reqwest::Request::new(Method::GET, Url::parse("https://bucket.s3.amazonaws.com/key").unwrap());
+        suite_signer("s3", None).sign_at(&mut request, SUITE_TIME).unwrap();
+        assert_eq!(
+            request.headers()["x-amz-content-sha256"],
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert!(request.headers()[AUTHORIZATION]
+            .to_str()
+            .unwrap()
+            .contains("SignedHeaders=host;x-amz-content-sha256;x-amz-date,"));
+
+        let mut request = reqwest::Request::new(Method::GET, Url::parse("https://example.amazonaws.com/").unwrap());
+        suite_signer("service", None).sign_at(&mut request, SUITE_TIME).unwrap();
+        assert!(!request.headers().contains_key("x-amz-content-sha256"));
+    }
+
+    fn hmac_signer(algorithm: HmacAlgorithm, template: &str, encoding: SignatureEncoding, prefix: &str) -> HmacSigner {
+        HmacSigner {
+            secret: b"secret".to_vec(),
+            algorithm,
+            canonical_template: template.into(),
+            signature_header: HeaderName::from_static("x-signature"),
+            signature_prefix: prefix.into(),
+            encoding,
+            timestamp_header: Some(HeaderName::from_static("x-timestamp")),
+        }
+    }
+
+    fn order_request() -> reqwest::Request {
+        let mut request =
+            reqwest::Request::new(Method::POST, Url::parse("https://api.example.com/v1/orders?b=2&a=1").unwrap());
+        *request.body_mut() = Some(reqwest::Body::from(r#"{"id":1}"#));
+        request
+    }
+
+    #[test]
+    fn hmac_default_template() {
+        let signer = hmac_signer(HmacAlgorithm::Sha256, DEFAULT_HMAC_TEMPLATE, SignatureEncoding::Hex, "sha256=");
+        let mut request = order_request();
+        assert_eq!(
+            signer.canonical_string(&request, 1_700_000_000_456).unwrap(),
+            "POST\n/v1/orders\nb=2&a=1\n1700000000\n037c9214eef74cc3887f3a4f085b4e17d76280dafd273b0ee160c09c4ba1cfd4"
+        );
+
+        signer.sign_at(&mut request, 1_700_000_000_456).unwrap();
+        assert_eq!(
+            request.headers()["x-signature"],
+            "sha256=c30b0583ae0a63051d2f0af78c5312c6cd25d7fd95633cb6278ffe15f67a660e"
+        );
+        assert_eq!(request.headers()["x-timestamp"], "1700000000");
+    }
+
+    #[test]
+    fn hmac_custom_template_keeps_unknown_placeholders() {
+        let signer =
+            hmac_signer(HmacAlgorithm::Sha512, "{timestamp}|{host}|{body}|{nonce}", SignatureEncoding::Base64, "");
+        let mut request = order_request();
+        signer.sign_at(&mut request, 1_700_000_123_999).unwrap();
+        assert_eq!(
+            request.headers()["x-signature"],
+            "ySeV5tOYx4yjRnsyoR4kUJciGWzgFboGk47ZgkMBBZ74WqXNRuoKdays3fwRz9u/mPb6BJ+MfoIuxgIGHPfahQ=="
+        );
+    }
+
+    #[test]
+    fn utc_timestamp_formats_civil_dates() {
+        let timestamp = |secs| utc_timestamp(secs).1;
+        assert_eq!(utc_timestamp(0), ("19700101".to_string(), "19700101T000000Z".to_string()));
+        assert_eq!(timestamp(SUITE_TIME), "20150830T123600Z");
+        assert_eq!(timestamp(951_782_400), "20000229T000000Z");
+        assert_eq!(timestamp(1_709_208_000), "20240229T120000Z");
+        assert_eq!(timestamp(1_709_251_199), "20240229T235959Z");
+        assert_eq!(timestamp(1_709_251_200), "20240301T000000Z");
+        assert_eq!(timestamp(4_107_542_399), "21000228T235959Z");
+        assert_eq!(timestamp(4_107_542_400), "21000301T000000Z");
+    }
+}
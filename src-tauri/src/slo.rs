@@ -0,0 +1,158 @@
+//! Pass/fail SLO thresholds evaluated on the final stats.
+//!
+//! Each threshold compares one metric of `LoadTestStats` against a value and reports the
+//! observed value, so CI pipelines can gate on the verdict without interpreting charts.
+//! Thresholds apply to the whole run; there are no per-endpoint stats to hold them against.
+
+use crate::{LoadTestError, LoadTestStats};
+use serde::{Deserialize, Serialize};
+
+/// Metric of the final stats a threshold applies to
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum SloMetric {
+    AvgResponseTimeMs,
+    P50Ms,
+    P90Ms,
+    P95Ms,
+    P99Ms,
+    MaxResponseTimeMs,
+    /// Failed requests as a percentage of completed requests
+    ErrorRatePercent,
+    RequestsPerSecond,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum SloComparison {
+    #[serde(rename = "<")]
+    LessThan,
+    #[serde(rename = "<=")]
+    LessThanOrEqual,
+    #[serde(rename = ">")]
+    GreaterThan,
+    #[serde(rename = ">=")]
+    GreaterThanOrEqual,
+}
+
+/// A single SLO, e.g. `P99Ms < 300`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SloThreshold {
+    pub metric: SloMetric,
+    pub comparison: SloComparison,
+    pub value: f64,
+    /// Endpoint the threshold is meant for (full URL or path, e.g. "/api/users"). This is a
+    /// guard, not a filter: a run targets a single URL, so every threshold is evaluated
+    /// against the whole run, and a config with a threshold for another endpoint is rejected
+    /// so a mistyped endpoint cannot pass silently. Split endpoints into separate runs (or a
+    /// batch) to hold them to different thresholds.
+    #[serde(default)]
+    pub endpoint: Option<String>,
+}
+
+/// Outcome of one threshold
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SloResult {
+    pub metric: SloMetric,
+    pub comparison: SloComparison,
+    pub threshold: f64,
+    pub observed: f64,
+    pub passed: bool,
+    #[serde(default)]
+    pub endpoint: Option<String>,
+}
+
+/// Overall verdict: passes when every threshold passes on a complete run
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SloVerdict {
+    pub passed: bool,
+    /// False when the run ended early (auto-stop or cancel); partial runs never pass
+    pub complete: bool,
+    pub results: Vec<SloResult>,
+}
+
+/// Checks threshold values, and that thresholds with an endpoint target the run's `url`
+pub(crate) fn validate(thresholds: &[SloThreshold], url: &str) -> Result<(), LoadTestError> {
+    let path = url::Url::parse(url).map(|parsed| parsed.path().to_string()).unwrap_or_default();
+    for threshold in thresholds {
+        if !threshold.value.is_finite() || threshold.value < 0.0 {
+            return Err(LoadTestError::InvalidConfig(
+                format!("SLO threshold for {:?} must be a non-negative number", threshold.metric)
+            ));
+        }
+        if let Some(endpoint) = threshold.endpoint.as_deref().filter(|endpoint| *endpoint != url && *endpoint != path) {
+            return Err(LoadTestError::InvalidConfig(format!(
+                "SLO threshold for {:?} targets endpoint '{}', but the test runs against '{}'",
+                threshold.metric, endpoint, url
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Evaluates the thresholds against the final stats
+pub(crate) fn evaluate(thresholds: &[SloThreshold], stats: &LoadTestStats) -> SloVerdict {
+    let results: Vec<SloResult> = thresholds
+        .iter()
+        .map(|threshold| {
+            let observed = observe(threshold.metric, stats);
+            let passed = match threshold.comparison {
+                SloComparison::LessThan => observed < threshold.value,
+                SloComparison::LessThanOrEqual => observed <= threshold.value,
+                SloComparison::GreaterThan => observed > threshold.value,
+                SloComparison::GreaterThanOrEqual => observed >= threshold.value,
+            };
+            SloResult {
+                metric: threshold.metric,
+                comparison: threshold.comparison,
+                threshold: threshold.value,
+                observed,
+                passed,
+                endpoint: threshold.endpoint.clone(),
+            }
+        })
+        .collect();
+
+    let completed = stats.successful_requests + stats.failed_requests + stats.warmup_requests;
+    let complete = stats.stop_reason.is_none() && completed >= stats.total_requests;
+    SloVerdict {
+        passed: complete && results.iter().all(|result| result.passed),
+        complete,
+        results,
+    }
+}
+
+fn observe(metric: SloMetric, stats: &LoadTestStats) -> f64 {
+    match metric {
+        SloMetric::AvgResponseTimeMs => stats.avg_response_time_ms,
+        SloMetric::P50Ms => stats.percentiles.p50,
+        SloMetric::P90Ms => stats.percentiles.p90,
+        SloMetric::P95Ms => stats.percentiles.p95,
+        SloMetric::P99Ms => stats.percentiles.p99,
+        SloMetric::MaxResponseTimeMs => stats.max_response_time_ms,
+        SloMetric::ErrorRatePercent => stats.error_rate_percent(),
+        SloMetric::RequestsPerSecond => stats.requests_per_second,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn threshold(endpoint: Option<&str>) -> SloThreshold {
+        SloThreshold {
+            metric: SloMetric::P99Ms,
+            comparison: SloComparison::LessThan,
+            value: 300.0,
+            endpoint: endpoint.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn validate_rejects_thresholds_for_other_endpoints() {
+        let url = "https://api.example.com/api/users?page=1";
+        assert!(validate(&[threshold(None)], url).is_ok());
+        assert!(validate(&[threshold(Some("/api/users"))], url).is_ok());
+        assert!(validate(&[threshold(Some(url))], url).is_ok());
+        assert!(validate(&[threshold(Some("/api/orders"))], url).is_err());
+        assert!(validate(&[threshold(Some("/api/users/"))], url).is_err());
+    }
+}
@@ -0,0 +1,110 @@
+//! Per-window latency percentiles and error rate.
+//!
+//! Results are grouped by completion time into fixed windows. The same aggregator closes
+//! windows live in the collector (streamed as "load-test-window") and rebuilds the full
+//! series from the sorted results in `calculate_stats`. The two can differ slightly: a
+//! window is streamed once its end has passed, so a result reaching the collector after
+//! that is counted in the window open at the time, while the final series puts it in the
+//! window it completed in. The final series in the stats is the reference.
+
+use crate::{calculate_percentile, RequestResult};
+use serde::{Deserialize, Serialize};
+
+/// Aggregates of the requests that completed in one window
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WindowStats {
+    /// Window start, seconds since test start
+    pub start_secs: f64,
+    /// Window end, seconds since test start
+    pub end_secs: f64,
+    pub requests: u32,
+    pub failed: u32,
+    pub error_rate_percent: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    /// Whether the window holds warm-up results
+    #[serde(default)]
+    pub warmup: bool,
+}
+
+/// Accumulates results into consecutive windows, closing a window once a later one starts
+pub(crate) struct WindowAggregator {
+    window_secs: f64,
+    /// Index of the open window
+    index: u64,
+    durations: Vec<f64>,
+    failed: u32,
+    warmup: bool,
+}
+
+impl WindowAggregator {
+    pub(crate) fn new(window_secs: f64) -> Self {
+        Self { window_secs, index: 0, durations: Vec::new(), failed: 0, warmup: false }
+    }
+
+    /// End of the open window in seconds since test start
+    pub(crate) fn open_window_end_secs(&self) -> f64 {
+        (self.index + 1) as f64 * self.window_secs
+    }
+
+    /// Adds a result and returns the windows it closed, including empty ones in between.
+    /// A result arriving after its window closed counts toward the open window.
+    pub(crate) fn observe(&mut self, result: &RequestResult) -> Vec<WindowStats> {
+        let index = (result.timestamp_ms / 1000.0 / self.window_secs) as u64;
+        let mut closed = Vec::new();
+        while self.index < index {
+            closed.push(self.close());
+        }
+        self.durations.push(result.duration_ms);
+        if !result.success {
+            self.failed += 1;
+        }
+        self.warmup |= result.warmup;
+        closed
+    }
+
+    /// Closes the open window and opens the next one
+    pub(crate) fn close(&mut self) -> WindowStats {
+        self.durations.sort_by(|a, b| a.total_cmp(b));
+        let requests = self.durations.len() as u32;
+        let window = WindowStats {
+            start_secs: self.index as f64 * self.window_secs,
+            end_secs: self.open_window_end_secs(),
+            requests,
+            failed: self.failed,
+            error_rate_percent: if requests == 0 { 0.0 } else { self.failed as f64 * 100.0 / requests as f64 },
+            p50_ms: calculate_percentile(&self.durations, 50.0),
+            p90_ms: calculate_percentile(&self.durations, 90.0),
+            p99_ms: calculate_percentile(&self.durations, 99.0),
+            max_ms: self.durations.last().copied().unwrap_or(0.0),
+            warmup: self.warmup,
+        };
+        self.index += 1;
+        self.durations.clear();
+        self.failed = 0;
+        self.warmup = false;
+        window
+    }
+
+    /// Closes the last window at the end of the run; None when it is empty
+    pub(crate) fn finish(mut self) -> Option<WindowStats> {
+        (!self.durations.is_empty()).then(|| self.close())
+    }
+}
+
+/// Builds the window series from results in timestamp order
+pub(crate) fn calculate_window_stats(
+    results: &[RequestResult],
+    sorted_by_timestamp: &[usize],
+    window_secs: f64,
+) -> Vec<WindowStats> {
+    let mut aggregator = WindowAggregator::new(window_secs);
+    let mut windows: Vec<WindowStats> = sorted_by_timestamp
+        .iter()
+        .flat_map(|&i| aggregator.observe(&results[i]))
+        .collect();
+    windows.extend(aggregator.finish());
+    windows
+}
@@ -31,6 +31,10 @@ function getErrorTypeLabel(errorType: ErrorType): string {
       return "HTTP";
     case "Redirect":
       return "Redirect";
+    case "PortExhaustion":
+      return "Ports";
+    case "Auth":
+      return "Auth";
     case "Other":
       return "Error";
     default:
@@ -52,6 +56,15 @@ function getErrorTypeStyle(errorType: ErrorType): string {
   }
 }
 
+/** Error message plus the captured response body, if any */
+function getErrorTooltip(log: ErrorLogEntry): string {
+  if (!log.response) {
+    return log.error;
+  }
+  const truncated = log.response.truncated ? `\n… (${log.response.body_size} bytes total)` : "";
+  return `${log.error}\n\n${log.response.body}${truncated}`;
+}
+
 /**
  * Memoized error log row component.
  */
@@ -73,7 +86,7 @@ const ErrorLogRow = memo(function ErrorLogRow({
           {log.status}
         </span>
       )}
-      <span className={styles.errorLogMessage} title={log.error}>
+      <span className={styles.errorLogMessage} title={getErrorTooltip(log)}>
         {log.error}
       </span>
       <span className={styles.errorLogDuration}>{formatMs(log.duration_ms)}</span>
@@ -5,8 +5,39 @@
 
 import { useCallback, useEffect, useRef } from "react";
 import { useTestConfigStore, useTestRunnerStore, useHistoryStore, useUIStore } from "../store";
-import { runLoadTest, cancelLoadTest, setupEventListeners } from "../services/tauri";
-import type { ProgressUpdate } from "../types/api";
+import {
+  runLoadTest,
+  runBatch,
+  runCapacitySearch,
+  cancelLoadTest,
+  cancelRun,
+  pauseLoadTest,
+  resumeLoadTest,
+  updateRunningTest,
+  listRuns,
+  getRunStatus,
+  listSavedRuns,
+  searchSavedRuns,
+  loadSavedRun,
+  deleteSavedRun,
+  tagSavedRun,
+  compareRuns,
+  setupEventListeners,
+} from "../services/tauri";
+import type {
+  BatchConfig,
+  BatchResult,
+  CapacityResult,
+  CapacitySearchConfig,
+  CompareOptions,
+  ComparisonReport,
+  HistoryQuery,
+  ProgressUpdate,
+  RunId,
+  RunStatus,
+  SavedRun,
+  SavedRunSummary,
+} from "../types/api";
 
 // Throttle progress updates to 5Hz (200ms) to reduce React re-renders
 // The backend already throttles to 20Hz, this further reduces UI updates
@@ -14,7 +45,8 @@ const PROGRESS_THROTTLE_MS = 200;
 
 /**
  * Hook that coordinates test execution between stores and services.
- * Returns run and cancel functions for controlling tests.
+ * Returns run, cancel, pause, resume and update functions for controlling tests,
+ * plus run registry queries. Control functions apply to all runs unless given a run id.
  */
 export function useTestRunner() {
   const getConfig = useTestConfigStore((s) => s.getConfig);
@@ -29,6 +61,11 @@ export function useTestRunner() {
   const pendingProgressRef = useRef<ProgressUpdate | null>(null);
   const throttleTimeoutRef = useRef<ReturnType<typeof setTimeout> | null>(null);
 
+  // The run started by runTest, recognized by the token it echoes in its started event;
+  // progress of other runs (batches, capacity searches, other windows) is ignored
+  const activeRunIdRef = useRef<RunId | null>(null);
+  const clientTokenRef = useRef<string | null>(null);
+
   // Setup event listeners on mount
   useEffect(() => {
     let cleanup: (() => void) | null = null;
@@ -37,6 +74,8 @@ export function useTestRunner() {
       cleanup = await setupEventListeners(
         // Progress handler with throttling
         (progress) => {
+          if (progress.run_id !== activeRunIdRef.current) return;
+
           const now = Date.now();
           const timeSinceLastUpdate = now - lastProgressUpdateRef.current;
 
@@ -72,11 +111,18 @@ export function useTestRunner() {
             }
           }
         },
-        // Cancel handler
-        () => {
-          setRunning(false);
+        // Cancel handler - the run still resolves with stats for the completed
+        // requests (`cancelled: true`), so only the live progress is cleared here
+        (runId) => {
+          if (runId !== activeRunIdRef.current) return;
           setProgress(null);
-          setError("Test cancelled by user.");
+        },
+        undefined,
+        // Started handler - claims the run started by runTest
+        (event) => {
+          if (clientTokenRef.current !== null && event.payload.client_token === clientTokenRef.current) {
+            activeRunIdRef.current = event.run_id;
+          }
         }
       );
     };
@@ -90,10 +136,11 @@ export function useTestRunner() {
         clearTimeout(throttleTimeoutRef.current);
       }
     };
-  }, [setProgress, setRunning, setError]);
+  }, [setProgress]);
 
   /**
-   * Validates that the URL is a proper HTTP/HTTPS URL.
+   * Validates that the URL is a proper HTTP/HTTPS (or http+unix) URL.
+   * Bare "host:port" targets are treated as http, matching the backend.
    */
   const validateUrl = (url: string): string | null => {
     if (!url.trim()) {
@@ -101,9 +148,9 @@ export function useTestRunner() {
     }
 
     try {
-      const parsed = new URL(url);
-      if (!["http:", "https:"].includes(parsed.protocol)) {
-        return "URL must use http:// or https:// protocol";
+      const parsed = new URL(url.includes("://") ? url : `http://${url}`);
+      if (!["http:", "https:", "http+unix:"].includes(parsed.protocol)) {
+        return "URL must use http://, https:// or http+unix:// protocol";
       }
       return null;
     } catch {
@@ -128,9 +175,13 @@ export function useTestRunner() {
     setError(null);
     selectEntry(null);
     setRunning(true);
+    const clientToken = crypto.randomUUID();
+    activeRunIdRef.current = null;
+    clientTokenRef.current = clientToken;
 
     try {
-      const stats = await runLoadTest(config);
+      // Runs started from the UI are kept in the run history
+      const stats = await runLoadTest({ ...config, save_to_history: true, client_token: clientToken });
       setStats(stats);
 
       // Add to history with current config state (extract state fields only)
@@ -161,6 +212,14 @@ export function useTestRunner() {
     } catch (e) {
       setError(String(e));
     } finally {
+      activeRunIdRef.current = null;
+      clientTokenRef.current = null;
+      // Drop a throttled update so it cannot land after the progress is cleared
+      if (throttleTimeoutRef.current) {
+        clearTimeout(throttleTimeoutRef.current);
+        throttleTimeoutRef.current = null;
+      }
+      pendingProgressRef.current = null;
       setRunning(false);
       setProgress(null);
     }
@@ -178,15 +237,181 @@ export function useTestRunner() {
   ]);
 
   /**
-   * Cancels the currently running test.
+   * Cancels the given run, or every running test.
    */
-  const cancelTest = useCallback(async () => {
+  const cancelTest = useCallback(async (runId?: RunId) => {
     try {
-      await cancelLoadTest();
+      await (runId === undefined ? cancelLoadTest() : cancelRun(runId));
     } catch {
       // Silently handle cancellation errors
     }
   }, []);
 
-  return { runTest, cancelTest };
+  /**
+   * Pauses the given run, or every running test.
+   */
+  const pauseTest = useCallback(async (runId?: RunId) => {
+    try {
+      await pauseLoadTest(runId);
+    } catch {
+      // Silently handle pause errors
+    }
+  }, []);
+
+  /**
+   * Resumes the given run, or every paused test.
+   */
+  const resumeTest = useCallback(async (runId?: RunId) => {
+    try {
+      await resumeLoadTest(runId);
+    } catch {
+      // Silently handle resume errors
+    }
+  }, []);
+
+  /**
+   * Changes concurrency and/or per-worker rate limit of the given run, or every running test.
+   */
+  const updateTest = useCallback(
+    async (concurrency?: number, rateLimit?: number, runId?: RunId) => {
+      try {
+        await updateRunningTest(concurrency, rateLimit, runId);
+      } catch (e) {
+        setError(String(e));
+      }
+    },
+    [setError]
+  );
+
+  /**
+   * Lists active runs, or the status of one run (null once it has finished).
+   */
+  const getRuns = useCallback(async (): Promise<RunStatus[]> => {
+    try {
+      return await listRuns();
+    } catch {
+      return [];
+    }
+  }, []);
+
+  const getRun = useCallback(async (runId: RunId): Promise<RunStatus | null> => {
+    try {
+      return await getRunStatus(runId);
+    } catch {
+      return null;
+    }
+  }, []);
+
+  /**
+   * Runs a batch of variants back to back; resolves to null when the batch fails.
+   */
+  const runBatchTest = useCallback(
+    async (batch: BatchConfig): Promise<BatchResult | null> => {
+      try {
+        return await runBatch(batch);
+      } catch (e) {
+        setError(String(e));
+        return null;
+      }
+    },
+    [setError]
+  );
+
+  /**
+   * Runs a capacity search; resolves to null when the search fails.
+   */
+  const findCapacity = useCallback(
+    async (search: CapacitySearchConfig): Promise<CapacityResult | null> => {
+      try {
+        return await runCapacitySearch(search);
+      } catch (e) {
+        setError(String(e));
+        return null;
+      }
+    },
+    [setError]
+  );
+
+  /**
+   * Lists runs saved in the backend history, newest first, filtered by the query when given.
+   */
+  const getSavedRuns = useCallback(async (query?: HistoryQuery): Promise<SavedRunSummary[]> => {
+    try {
+      return await (query ? searchSavedRuns(query) : listSavedRuns());
+    } catch {
+      return [];
+    }
+  }, []);
+
+  const getSavedRun = useCallback(
+    async (id: number, includeResults = false): Promise<SavedRun | null> => {
+      try {
+        return await loadSavedRun(id, includeResults);
+      } catch (e) {
+        setError(String(e));
+        return null;
+      }
+    },
+    [setError]
+  );
+
+  const removeSavedRun = useCallback(
+    async (id: number): Promise<boolean> => {
+      try {
+        await deleteSavedRun(id);
+        return true;
+      } catch (e) {
+        setError(String(e));
+        return false;
+      }
+    },
+    [setError]
+  );
+
+  /**
+   * Replaces the tags of a saved run; resolves to the stored tags, or null on failure.
+   */
+  const setSavedRunTags = useCallback(
+    async (id: number, tags: string[]): Promise<string[] | null> => {
+      try {
+        return await tagSavedRun(id, tags);
+      } catch (e) {
+        setError(String(e));
+        return null;
+      }
+    },
+    [setError]
+  );
+
+  /**
+   * Compares a candidate saved run with a baseline; resolves to null when the comparison fails.
+   */
+  const compareSavedRuns = useCallback(
+    async (baselineId: number, candidateId: number, options?: CompareOptions): Promise<ComparisonReport | null> => {
+      try {
+        return await compareRuns(baselineId, candidateId, options);
+      } catch (e) {
+        setError(String(e));
+        return null;
+      }
+    },
+    [setError]
+  );
+
+  return {
+    runTest,
+    cancelTest,
+    pauseTest,
+    resumeTest,
+    updateTest,
+    getRuns,
+    getRun,
+    runBatchTest,
+    findCapacity,
+    getSavedRuns,
+    getSavedRun,
+    removeSavedRun,
+    setSavedRunTags,
+    compareSavedRuns,
+  };
 }
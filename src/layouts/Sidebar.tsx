@@ -51,7 +51,7 @@ export function Sidebar() {
 
         {/* Sticky footer with Run button */}
         <div className={styles.sidebarFooter}>
-          <RunButton onRun={runTest} onCancel={cancelTest} />
+          <RunButton onRun={runTest} onCancel={() => cancelTest()} />
         </div>
       </form>
 
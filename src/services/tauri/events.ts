@@ -3,23 +3,33 @@
  */
 
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
-import type { ProgressUpdate } from "../../types/api";
+import type { ProgressUpdate, RunEvent, RunId, RunStarted, WindowStats } from "../../types/api";
 
 /** Event handler for progress updates */
 export type ProgressHandler = (progress: ProgressUpdate) => void;
 
-/** Event handler for cancellation */
-export type CancelHandler = () => void;
+/** Event handler for cancellation of a run */
+export type CancelHandler = (runId: RunId) => void;
+
+/** Event handler for a run starting */
+export type StartedHandler = (event: RunEvent<RunStarted>) => void;
+
+/** Event handler for closed stats windows, streamed while a test runs */
+export type WindowHandler = (event: RunEvent<WindowStats>) => void;
 
 /**
  * Sets up event listeners for load test events.
  * @param onProgress - Handler for progress updates
  * @param onCancel - Handler for test cancellation
+ * @param onWindow - Optional handler for per-window latency and error rate
+ * @param onStarted - Optional handler for runs starting, to learn their run ids
  * @returns Cleanup function to remove listeners
  */
 export async function setupEventListeners(
   onProgress: ProgressHandler,
-  onCancel: CancelHandler
+  onCancel: CancelHandler,
+  onWindow?: WindowHandler,
+  onStarted?: StartedHandler
 ): Promise<UnlistenFn> {
   try {
     const unlisteners: UnlistenFn[] = [];
@@ -29,9 +39,25 @@ export async function setupEventListeners(
     );
     unlisteners.push(unlistenProgress);
 
-    const unlistenCancel = await listen("load-test-cancelled", () => onCancel());
+    const unlistenCancel = await listen<RunEvent<null>>("load-test-cancelled", (event) =>
+      onCancel(event.payload.run_id)
+    );
     unlisteners.push(unlistenCancel);
 
+    if (onWindow) {
+      const unlistenWindow = await listen<RunEvent<WindowStats>>("load-test-window", (event) =>
+        onWindow(event.payload)
+      );
+      unlisteners.push(unlistenWindow);
+    }
+
+    if (onStarted) {
+      const unlistenStarted = await listen<RunEvent<RunStarted>>("load-test-started", (event) =>
+        onStarted(event.payload)
+      );
+      unlisteners.push(unlistenStarted);
+    }
+
     // Return combined cleanup function
     return () => {
       unlisteners.forEach((unlisten) => unlisten());
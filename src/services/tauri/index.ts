@@ -8,10 +8,32 @@
  */
 
 /** Execute a load test via Rust backend */
-export { runLoadTest, cancelLoadTest, getCpuCount } from "./loadTest";
+export {
+  runLoadTest,
+  runBatch,
+  runCapacitySearch,
+  cancelLoadTest,
+  cancelRun,
+  pauseLoadTest,
+  resumeLoadTest,
+  updateRunningTest,
+  listRuns,
+  getRunStatus,
+  getCpuCount,
+} from "./loadTest";
+
+/** Runs saved in the backend history database */
+export {
+  listSavedRuns,
+  searchSavedRuns,
+  loadSavedRun,
+  deleteSavedRun,
+  tagSavedRun,
+  compareRuns,
+} from "./runHistory";
 
 /** Setup event listeners for backend events */
 export { setupEventListeners } from "./events";
 
 /** Event handler types */
-export type { ProgressHandler, CancelHandler } from "./events";
+export type { ProgressHandler, CancelHandler, WindowHandler, StartedHandler } from "./events";
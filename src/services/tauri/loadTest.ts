@@ -4,7 +4,16 @@
  */
 
 import { invoke } from "@tauri-apps/api/core";
-import type { TestConfig, LoadTestStats } from "../../types/api";
+import type {
+  TestConfig,
+  LoadTestStats,
+  RunId,
+  RunStatus,
+  BatchConfig,
+  BatchResult,
+  CapacitySearchConfig,
+  CapacityResult,
+} from "../../types/api";
 
 /**
  * Checks if running in Tauri environment.
@@ -26,14 +35,112 @@ export async function runLoadTest(config: TestConfig): Promise<LoadTestStats> {
 }
 
 /**
- * Cancels a running load test.
- * Sets the cancellation flag in the backend.
+ * Runs the variants of a batch sequentially with a cooldown between them.
+ * Cancelling the running variant ends the batch.
+ * @param batch - Explicit configs and/or a parameter sweep
+ * @returns Promise resolving to per-variant stats and a summary table
  */
-export async function cancelLoadTest(): Promise<void> {
+export async function runBatch(batch: BatchConfig): Promise<BatchResult> {
+  if (!isTauri()) {
+    throw new Error("Load testing requires the Tauri app. Run with: npm run tauri dev");
+  }
+  return invoke<BatchResult>("run_batch", { batch });
+}
+
+/**
+ * Searches for the highest arrival rate the target sustains within the given limits.
+ * Cancelling the running step ends the search.
+ * @param search - Base config, rate range, strategy and pass limits
+ * @returns Promise resolving to the rate curve and the breakpoint
+ */
+export async function runCapacitySearch(search: CapacitySearchConfig): Promise<CapacityResult> {
+  if (!isTauri()) {
+    throw new Error("Load testing requires the Tauri app. Run with: npm run tauri dev");
+  }
+  return invoke<CapacityResult>("run_capacity_search", { search });
+}
+
+/**
+ * Cancels all running load tests.
+ * Each running test resolves with partial stats.
+ * @param drainInFlight - Let requests already sent complete instead of abandoning them
+ */
+export async function cancelLoadTest(drainInFlight = false): Promise<void> {
+  if (!isTauri()) {
+    return;
+  }
+  return invoke("cancel_load_test", { drainInFlight });
+}
+
+/**
+ * Cancels a single run.
+ * @param runId - Run to cancel
+ * @param drainInFlight - Let requests already sent complete instead of abandoning them
+ */
+export async function cancelRun(runId: RunId, drainInFlight = false): Promise<void> {
+  if (!isTauri()) {
+    return;
+  }
+  return invoke("cancel_run", { runId, drainInFlight });
+}
+
+/**
+ * Pauses a running load test. New requests are held until resumed;
+ * paused time is excluded from rates.
+ * @param runId - Run to pause (omit for all runs)
+ */
+export async function pauseLoadTest(runId?: RunId): Promise<void> {
   if (!isTauri()) {
     return;
   }
-  return invoke("cancel_load_test");
+  return invoke("pause_load_test", { runId });
+}
+
+/**
+ * Resumes a paused load test.
+ * @param runId - Run to resume (omit for all runs)
+ */
+export async function resumeLoadTest(runId?: RunId): Promise<void> {
+  if (!isTauri()) {
+    return;
+  }
+  return invoke("resume_load_test", { runId });
+}
+
+/**
+ * Changes the worker count and/or per-worker rate limit of a running test.
+ * @param concurrency - New number of workers (omit to keep)
+ * @param rateLimit - New rate limit in requests/second per worker, 0 for unlimited (omit to keep)
+ * @param runId - Run to update (omit for all runs)
+ */
+export async function updateRunningTest(concurrency?: number, rateLimit?: number, runId?: RunId): Promise<void> {
+  if (!isTauri()) {
+    return;
+  }
+  return invoke("update_running_test", { runId, concurrency, rateLimit });
+}
+
+/**
+ * Lists the active runs, oldest first.
+ * @returns Promise resolving to the run statuses
+ */
+export async function listRuns(): Promise<RunStatus[]> {
+  if (!isTauri()) {
+    return [];
+  }
+  return invoke<RunStatus[]>("list_runs");
+}
+
+/**
+ * Gets the status of an active run.
+ * @param runId - Run to look up
+ * @returns Promise resolving to the run status; rejects when the run is not active
+ */
+export async function getRunStatus(runId: RunId): Promise<RunStatus> {
+  if (!isTauri()) {
+    throw new Error("Load testing requires the Tauri app. Run with: npm run tauri dev");
+  }
+  return invoke<RunStatus>("get_run_status", { runId });
 }
 
 /**
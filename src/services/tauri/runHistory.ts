@@ -0,0 +1,101 @@
+/**
+ * Run History Service - Tauri API calls for runs saved in the backend database
+ * Pure functions with no side effects beyond API calls.
+ */
+
+import { invoke } from "@tauri-apps/api/core";
+import type {
+  CompareOptions,
+  ComparisonReport,
+  HistoryQuery,
+  SavedRun,
+  SavedRunSummary,
+} from "../../types/api";
+
+/**
+ * Checks if running in Tauri environment.
+ */
+function isTauri(): boolean {
+  return typeof window !== "undefined" && "__TAURI_INTERNALS__" in window;
+}
+
+/**
+ * Lists saved runs, newest first.
+ * @param limit - Page size (default 50)
+ * @param offset - Runs to skip
+ * @returns Promise resolving to the run summaries
+ */
+export async function listSavedRuns(limit?: number, offset?: number): Promise<SavedRunSummary[]> {
+  if (!isTauri()) {
+    return [];
+  }
+  return invoke<SavedRunSummary[]>("list_saved_runs", { limit, offset });
+}
+
+/**
+ * Finds saved runs by URL or tag text, tags and start time, newest first.
+ * @param query - Filters and paging
+ * @returns Promise resolving to the matching run summaries
+ */
+export async function searchSavedRuns(query: HistoryQuery): Promise<SavedRunSummary[]> {
+  if (!isTauri()) {
+    return [];
+  }
+  return invoke<SavedRunSummary[]>("search_saved_runs", { query });
+}
+
+/**
+ * Loads a saved run with its config and stats.
+ * @param id - Saved run id
+ * @param includeResults - Also load the raw results, when they were saved
+ * @returns Promise resolving to the saved run; rejects when it does not exist
+ */
+export async function loadSavedRun(id: number, includeResults = false): Promise<SavedRun> {
+  if (!isTauri()) {
+    throw new Error("Run history requires the Tauri app. Run with: npm run tauri dev");
+  }
+  return invoke<SavedRun>("load_saved_run", { id, includeResults });
+}
+
+/**
+ * Deletes a saved run.
+ * @param id - Saved run id
+ */
+export async function deleteSavedRun(id: number): Promise<void> {
+  if (!isTauri()) {
+    return;
+  }
+  return invoke("delete_saved_run", { id });
+}
+
+/**
+ * Replaces the tags of a saved run.
+ * @param id - Saved run id
+ * @param tags - New tags
+ * @returns Promise resolving to the tags as stored (trimmed, deduplicated, sorted)
+ */
+export async function tagSavedRun(id: number, tags: string[]): Promise<string[]> {
+  if (!isTauri()) {
+    return tags;
+  }
+  return invoke<string[]>("tag_saved_run", { id, tags });
+}
+
+/**
+ * Compares a candidate run with a baseline run and flags significant regressions.
+ * Latency significance needs both runs saved with raw results.
+ * @param baselineId - Saved run id of the baseline
+ * @param candidateId - Saved run id of the candidate
+ * @param options - Significance level, minimum change and bootstrap resamples
+ * @returns Promise resolving to the comparison report
+ */
+export async function compareRuns(
+  baselineId: number,
+  candidateId: number,
+  options?: CompareOptions
+): Promise<ComparisonReport> {
+  if (!isTauri()) {
+    throw new Error("Run history requires the Tauri app. Run with: npm run tauri dev");
+  }
+  return invoke<ComparisonReport>("compare_runs", { baselineId, candidateId, options });
+}
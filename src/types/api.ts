@@ -6,6 +6,138 @@
 /** HTTP methods supported by the load tester */
 export type HttpMethod = "GET" | "POST" | "PUT" | "DELETE" | "PATCH" | "HEAD" | "OPTIONS";
 
+/** How HTTP/2 is negotiated when use_http2 is enabled */
+export type Http2Mode =
+  | "PriorKnowledge" // Speak HTTP/2 immediately (h2c prior knowledge for http URLs)
+  | "Upgrade" // One Upgrade: h2c handshake before the test, then h2c prior knowledge if accepted
+  // (HTTP/1.1 if declined); http URLs without a proxy only
+  | "Alpn"; // Negotiate via TLS ALPN (https only)
+
+/** Which IP address families to connect to when resolving the target host */
+export type IpPreference = "Any" | "PreferV4" | "PreferV6" | "V4Only" | "V6Only";
+
+/** Content-Encoding applied to the request body */
+export type BodyCompression = "None" | "Gzip" | "Brotli" | "Zstd";
+
+/** Capture of response headers and body prefixes */
+export interface ResponseCaptureConfig {
+  /** Maximum body bytes kept per response (default 2048) */
+  max_body_bytes?: number;
+  /** Fraction of successful responses to capture as well (0-1, default 0) */
+  success_sample_rate?: number;
+  /** Total bytes kept across the run (default 16 MiB); further captures are dropped */
+  memory_budget_bytes?: number;
+}
+
+/** Safety thresholds that abort a running test. Unset thresholds are not checked. */
+export interface AutoStopConfig {
+  /** Stop when the error rate over the window exceeds this percentage */
+  max_error_rate_percent?: number | null;
+  /** Stop when p95 latency over the window exceeds this many milliseconds */
+  max_p95_ms?: number | null;
+  /** Stop after this many connection errors in a row */
+  max_consecutive_connection_errors?: number | null;
+  /** Sliding window for the error rate and p95 checks (default 10) */
+  window_secs?: number;
+  /** Results required in the window before error rate and p95 are evaluated (default 20) */
+  min_requests?: number;
+}
+
+/** Why a test was stopped before issuing all requests */
+export type StopReason =
+  | { type: "ErrorRate"; error_rate_percent: number; threshold_percent: number; window_secs: number }
+  | { type: "LatencyP95"; p95_ms: number; threshold_ms: number; window_secs: number }
+  | { type: "ConsecutiveConnectionErrors"; count: number };
+
+/** Metric of the final stats an SLO threshold applies to */
+export type SloMetric =
+  | "AvgResponseTimeMs"
+  | "P50Ms"
+  | "P90Ms"
+  | "P95Ms"
+  | "P99Ms"
+  | "MaxResponseTimeMs"
+  | "ErrorRatePercent" // Failed requests as a percentage of completed requests
+  | "RequestsPerSecond";
+
+export type SloComparison = "<" | "<=" | ">" | ">=";
+
+/** A single SLO, e.g. P99Ms < 300 */
+export interface SloThreshold {
+  metric: SloMetric;
+  comparison: SloComparison;
+  value: number;
+  /** Endpoint the threshold is meant for (full URL or path). Only a guard: thresholds apply to the whole run, and one for another URL rejects the config */
+  endpoint?: string | null;
+}
+
+/** Outcome of one SLO threshold */
+export interface SloResult {
+  metric: SloMetric;
+  comparison: SloComparison;
+  threshold: number;
+  observed: number;
+  passed: boolean;
+  endpoint: string | null;
+}
+
+/** Overall SLO verdict: passes when every threshold passes on a complete run */
+export interface SloVerdict {
+  passed: boolean;
+  /** False when the run ended early (auto-stop or cancel); partial runs never pass */
+  complete: boolean;
+  results: SloResult[];
+}
+
+/** How cookies set by the target are stored and sent back */
+export type CookieMode =
+  | "Disabled" // Set-Cookie responses are ignored
+  | "Shared" // One cookie jar shared by all workers
+  | "PerVirtualUser"; // One cookie jar per worker, isolating sessions
+
+/** Authentication applied to every request */
+export type AuthConfig =
+  | { type: "Basic"; username: string; password: string }
+  | { type: "Bearer"; token: string }
+  | { type: "Digest"; username: string; password: string }
+  | {
+      type: "OAuth2ClientCredentials";
+      token_url: string;
+      client_id: string;
+      client_secret: string;
+      scope?: string | null;
+      /** Send the client credentials in the form body instead of a Basic auth header */
+      credentials_in_body?: boolean;
+    };
+
+/** Request signing, applied after authentication */
+export type SigningConfig =
+  | {
+      type: "Hmac";
+      secret: string;
+      algorithm?: "Sha256" | "Sha512";
+      /**
+       * String to sign. Placeholders: {method}, {path}, {query}, {host}, {url},
+       * {timestamp}, {timestamp_ms}, {body}, {body_sha256}
+       */
+      canonical_template?: string;
+      /** Header that receives the signature (default X-Signature) */
+      signature_header?: string;
+      /** Prefix prepended to the signature value (e.g. "sha256=") */
+      signature_prefix?: string;
+      encoding?: "Hex" | "Base64";
+      /** Header that receives the timestamp used in the signature */
+      timestamp_header?: string | null;
+    }
+  | {
+      type: "AwsSigV4";
+      access_key_id: string;
+      secret_access_key: string;
+      session_token?: string | null;
+      region: string;
+      service: string;
+    };
+
 /** Custom HTTP header for requests */
 export interface CustomHeader {
   id: string;
@@ -34,7 +166,7 @@ export interface TestConfig {
   follow_redirects: boolean;
   /** Timeout for each request in seconds. 0 means infinite. */
   timeout_secs: number;
-  /** Rate limit in queries per second per worker. 0 means no limit. */
+  /** Rate limit in queries per second per worker (at least 0.001). 0 means no limit. */
   rate_limit: number;
   randomize_user_agent: boolean;
   randomize_headers: boolean;
@@ -43,16 +175,83 @@ export interface TestConfig {
   disable_keep_alive: boolean;
   /** Number of worker threads to use. 0 means use all available CPU cores. */
   worker_threads: number;
-  /** HTTP proxy address in format "host:port" or "http://host:port". Empty string means no proxy. */
+  /** Proxy address ("host:port" or http/https/socks5/socks5h URL, credentials allowed). Empty string means no proxy. */
   proxy_url: string;
+  /** Additional proxies to rotate requests across, round-robin with one client per proxy. */
+  proxy_urls?: string[];
+  /** Proxy basic auth username, used for proxies without credentials in their URL. */
+  proxy_username?: string | null;
+  /** Proxy basic auth password. */
+  proxy_password?: string | null;
+  /** Hosts that bypass the proxy (NO_PROXY syntax). */
+  no_proxy?: string[];
   /** Request body payload (optional, used for POST, PUT, PATCH methods). Empty string means no body. */
   body?: string | null;
   /** Content-Type header value (optional, auto-detected from body if not provided). */
   payload_content_type?: string | null;
   /** Form fields for multipart/form-data (optional, alternative to body). If set, body is ignored. */
   form_fields?: FormFieldConfig[];
+  /** Unix domain socket path to connect through instead of TCP (Unix only). */
+  unix_socket_path?: string | null;
+  /** How HTTP/2 is negotiated when use_http2 is enabled. */
+  http2_mode?: Http2Mode;
+  /** Number of HTTP/2 connections to spread requests across. 0 means a single shared client. */
+  http2_connections?: number;
+  /** Maximum concurrent streams per HTTP/2 connection. 0 means no limit besides concurrency. */
+  http2_max_streams_per_connection?: number;
+  /** Local source IPs to bind outgoing connections to, requests spread round-robin. */
+  local_addresses?: string[];
+  /** IPv4/IPv6 preference when resolving the target host. */
+  ip_preference?: IpPreference;
+  /** Authentication applied to every request (Basic, Bearer, Digest or OAuth2). */
+  auth?: AuthConfig | null;
+  /** Request signing (HMAC or AWS SigV4), applied after authentication. */
+  signing?: SigningConfig | null;
+  /** Cookie handling: disabled, one shared jar, or one jar per worker (virtual user). */
+  cookie_mode?: CookieMode;
+  /** Cookies every jar starts with, in Set-Cookie syntax (e.g. "session=abc; Path=/"). */
+  seed_cookies?: string[];
+  /** Compress the request body (sets Content-Encoding). Not supported with form fields. */
+  request_compression?: BodyCompression;
+  /** Send Accept-Encoding and decode compressed responses, reporting wire and decoded sizes. */
+  decompress_responses?: boolean;
+  /** Keep response headers and the start of the body for failed (and sampled) requests. */
+  response_capture?: ResponseCaptureConfig | null;
+  /** Safety thresholds that abort the test early (error rate, p95, connection errors). */
+  auto_stop?: AutoStopConfig | null;
+  /** Pass/fail thresholds on the final stats (e.g. p99 < 300ms), reported as a verdict. All apply to the whole run. */
+  slo_thresholds?: SloThreshold[];
+  /** Requests started in the first seconds are sent but kept out of aggregate stats (0 = off) */
+  warmup_secs?: number;
+  /** The first N results are kept out of aggregate stats (0 = off) */
+  warmup_requests?: number;
+  /** Delay each virtual user waits after a response before its next request */
+  think_time?: ThinkTime;
+  /** Start each iteration of a virtual user every N seconds, regardless of response time (0 = off, at most 3600) */
+  pacing_secs?: number;
+  /** Window length of the percentile and error rate series (0.1 to 3600 seconds, default 1) */
+  stats_window_secs?: number;
+  /** Save the run to the local history database (default false); body and form values are redacted */
+  save_to_history?: boolean;
+  /** Also save every request result, so results can be loaded from history later */
+  save_raw_results?: boolean;
+  /** Tags stored with the saved run */
+  history_tags?: string[];
+  /** Leave results out of the returned stats (load them from history instead) */
+  omit_results?: boolean;
+  /** Opaque value echoed in the run's started event, to tell it apart from other runs */
+  client_token?: string;
 }
 
+/** Think time distribution, in milliseconds (each value at most 3600000) */
+export type ThinkTime =
+  | { type: "None" }
+  | { type: "Constant"; ms: number }
+  | { type: "Uniform"; min_ms: number; max_ms: number }
+  /** Negative samples are clamped to 0 */
+  | { type: "Normal"; mean_ms: number; std_dev_ms: number }
+  | { type: "Exponential"; mean_ms: number };
+
 /** Error type classification for failed requests */
 export type ErrorType =
   | "None" // No error - request succeeded
@@ -61,6 +260,8 @@ export type ErrorType =
   | "Request" // Request was built/sent incorrectly
   | "Response" // Server returned an error response (4xx, 5xx)
   | "Redirect" // Redirect error (too many redirects, redirect loop)
+  | "PortExhaustion" // No local address/ephemeral port available (EADDRNOTAVAIL)
+  | "Auth" // Credentials could not be obtained (OAuth2 token fetch) - request not sent
   | "Other"; // Other/unknown error
 
 /** Result of a single HTTP request */
@@ -72,6 +273,22 @@ export interface RequestResult {
   /** Categorized error type for easier analysis */
   error_type: ErrorType;
   timestamp_ms: number;
+  /** Index into the proxy list when rotating across proxies */
+  proxy_index?: number;
+  /** Response body bytes as received (before decoding) */
+  body_wire_bytes?: number;
+  /** Response body bytes after decoding */
+  body_decoded_bytes?: number;
+  /** Time spent decoding a compressed response body */
+  decode_time_ms?: number;
+  /** Request size as issued: request line, headers and body (0 if never sent) */
+  request_bytes?: number;
+  /** Response size: status line, headers and body (0 without a response) */
+  response_bytes?: number;
+  /** Response headers and body prefix, when response capture selected this request */
+  captured_response?: CapturedResponse;
+  /** Part of the warm-up phase: shown in time series, excluded from aggregates */
+  warmup?: boolean;
 }
 
 /** Histogram bucket for response time distribution */
@@ -98,11 +315,63 @@ export interface StatusCodeCount {
   count: number;
 }
 
+/** Change to a running test's settings, marked on the time series */
+export interface Annotation {
+  /** Seconds since test start */
+  time_secs: number;
+  message: string;
+  /** Worker count after the change */
+  concurrency: number;
+  /** Rate limit per worker after the change (0 = unlimited) */
+  rate_limit: number;
+}
+
+/** Span of wall time during which the test was paused */
+export interface PauseInterval {
+  start_secs: number;
+  end_secs: number;
+}
+
+/** Response size distribution in bytes (status line, headers and body) */
+export interface SizePercentiles {
+  avg: number;
+  p50: number;
+  p90: number;
+  p95: number;
+  p99: number;
+  max: number;
+}
+
 /** Throughput data point for time series chart */
 export interface ThroughputDataPoint {
   time_secs: number;
   requests_completed: number;
   rps: number;
+  /** Whether the test was paused during (part of) this bucket */
+  paused?: boolean;
+  /** Request bytes sent in this bucket, in MB/s */
+  sent_mb_per_sec?: number;
+  /** Response bytes received in this bucket, in MB/s */
+  received_mb_per_sec?: number;
+  /** Whether the bucket holds warm-up results */
+  warmup?: boolean;
+}
+
+/** Latency percentiles and error rate of the requests completed in one window */
+export interface WindowStats {
+  /** Window start, seconds since test start */
+  start_secs: number;
+  /** Window end, seconds since test start */
+  end_secs: number;
+  requests: number;
+  failed: number;
+  error_rate_percent: number;
+  p50_ms: number;
+  p90_ms: number;
+  p99_ms: number;
+  max_ms: number;
+  /** Whether the window holds warm-up results */
+  warmup?: boolean;
 }
 
 /** Latency data point for time series chart */
@@ -110,6 +379,7 @@ export interface LatencyDataPoint {
   request_num: number;
   latency_ms: number;
   timestamp_ms: number;
+  warmup?: boolean;
 }
 
 /** Concurrency data point showing concurrent requests over time */
@@ -131,10 +401,81 @@ export interface ErrorLogEntry {
   error: string;
   error_type: ErrorType;
   duration_ms: number;
+  /** Captured response headers and body, when response capture is enabled */
+  response?: CapturedResponse;
+}
+
+/** Response headers and the first bytes of the (decoded) body */
+export interface CapturedResponse {
+  headers: CustomHeader[];
+  /** Body prefix, lossily converted to UTF-8 */
+  body: string;
+  /** Full body size in bytes */
+  body_size: number;
+  truncated: boolean;
+}
+
+/** Failed requests sharing the same error type, status and normalized message */
+export interface ErrorGroup {
+  /** Stable key: error type, status and normalized message */
+  fingerprint: string;
+  error_type: ErrorType;
+  status: number;
+  /** Message with URLs replaced by `<url>` and hex IDs and numbers by `#` */
+  message: string;
+  count: number;
+  first_seen_ms: number;
+  last_seen_ms: number;
+  /** First few occurrences, with their original messages */
+  examples: ErrorLogEntry[];
+}
+
+/** Totals for response capture */
+export interface CaptureStats {
+  captured: number;
+  /** Responses, or their copies in error logs and group examples, dropped because the memory budget was spent */
+  dropped_over_budget: number;
+  /** Bytes held by captures, including their copies */
+  bytes_used: number;
+}
+
+/** Results for a single proxy (credentials redacted) */
+export interface ProxyStats {
+  proxy: string;
+  total_requests: number;
+  successful_requests: number;
+  failed_requests: number;
+  avg_response_time_ms: number;
+  p50_response_time_ms: number;
+  p95_response_time_ms: number;
+}
+
+/** Authentication activity, reported separately from target failures */
+export interface AuthStats {
+  token_fetches: number;
+  token_fetch_failures: number;
+  last_token_error: string | null;
+  digest_challenges: number;
+}
+
+/** Request compression and response decoding totals */
+export interface CompressionStats {
+  request_encoding: string | null;
+  request_body_bytes: number;
+  request_wire_bytes: number;
+  compressed_responses: number;
+  response_wire_bytes: number;
+  response_decoded_bytes: number;
+  /** Decoded / wire bytes for compressed responses (0 when none) */
+  response_compression_ratio: number;
+  avg_decode_time_ms: number;
+  max_decode_time_ms: number;
 }
 
 /** Complete statistics from a load test run */
 export interface LoadTestStats {
+  /** Run these stats belong to */
+  run_id?: RunId;
   total_requests: number;
   successful_requests: number;
   failed_requests: number;
@@ -152,17 +493,349 @@ export interface LoadTestStats {
   error_logs: ErrorLogEntry[];
   concurrency_over_time: ConcurrencyDataPoint[];
   request_timeline: RequestTimelinePoint[];
+  /** Number of connections actually opened by the client during the test */
+  connections_opened?: number;
+  /** HTTP version of the first response received (e.g. "HTTP/2.0") */
+  http_version?: string | null;
+  /** Per-proxy breakdown, one entry per configured proxy */
+  proxy_stats?: ProxyStats[];
+  /** Token fetches and Digest challenges, present when authentication is configured */
+  auth_stats?: AuthStats | null;
+  compression?: CompressionStats | null;
+  /** Total request bytes sent (request lines, headers and bodies) */
+  total_bytes_sent?: number;
+  /** Total response bytes received (status lines, headers and bodies) */
+  total_bytes_received?: number;
+  /** Average upload bandwidth over the test, in MB/s */
+  sent_mb_per_sec?: number;
+  /** Average download bandwidth over the test, in MB/s */
+  received_mb_per_sec?: number;
+  /** Response size distribution in bytes */
+  response_size_percentiles?: SizePercentiles;
+  capture_stats?: CaptureStats | null;
+  /** Failures grouped by fingerprint, most frequent first */
+  error_groups?: ErrorGroup[];
+  /** Failures not grouped because the group limit was reached */
+  ungrouped_errors?: number;
+  /** Set when an auto-stop threshold ended the test early (stats are partial) */
+  stop_reason?: StopReason | null;
+  /** True when the user cancelled the test; stats cover the completed requests only */
+  cancelled?: boolean;
+  /** Wall time spent paused, excluded from requests per second and bandwidth */
+  paused_time_secs?: number;
+  /** When the test was paused, in seconds since test start */
+  pause_intervals?: PauseInterval[];
+  /** Concurrency and rate changes made while the test ran */
+  annotations?: Annotation[];
+  /** SLO pass/fail verdict, present when thresholds are configured */
+  slo_verdict?: SloVerdict | null;
+  /** Warm-up results, excluded from counts, latencies, rates and error groups */
+  warmup_requests?: number;
+  /** Start of the measured phase in seconds since test start; rates are computed from here */
+  measurement_start_secs?: number;
+  /** p50/p90/p99/max latency and error rate per window of completion time */
+  window_stats?: WindowStats[];
+  /** Id of the run in the history database, when it was saved */
+  history_id?: number | null;
+  /** Why saving to history failed; the run itself completed */
+  history_error?: string | null;
 }
 
 /** Real-time progress update during test execution */
 export interface ProgressUpdate {
+  /** Run the update belongs to */
+  run_id: RunId;
   completed: number;
   total: number;
   successful: number;
   failed: number;
+  /** Cumulative rate: completed requests over active (unpaused) time */
   current_rps: number;
   elapsed_secs: number;
   latest_response_time_ms: number;
+  /** Length of the sliding window the window_* fields cover (shorter early in the test) */
+  window_secs?: number;
+  /** Requests per second completed within the sliding window */
+  window_rps?: number;
+  window_p50_ms?: number;
+  window_p95_ms?: number;
+  window_p99_ms?: number;
+  window_error_rate_percent?: number;
+  /** Requests sent and awaiting a response */
+  in_flight?: number;
+  /** Responses per status code so far (0 = no response) */
+  status_codes?: StatusCodeCount[];
+}
+
+/** Base config run once per combination of the non-empty axes */
+export interface ParameterSweep {
+  base: TestConfig;
+  concurrency?: number[];
+  /** Rate limits per worker in requests per second (0 = unlimited) */
+  rate_limit?: number[];
+  /** Request body sizes; each variant sends a generated body of exactly this many bytes (at most 16 MiB, not with GET/HEAD) */
+  payload_size_bytes?: number[];
+}
+
+/** Variants run back to back by run_batch */
+export interface BatchConfig {
+  /** Variants to run as-is, in order */
+  configs?: TestConfig[];
+  /** Variants generated from a base config, run after configs */
+  sweep?: ParameterSweep | null;
+  /** Pause between variants so the target can settle (at most 3600) */
+  cooldown_secs?: number;
+}
+
+/** One batch variant: stats, or the error that kept it from starting */
+export interface BatchVariantResult {
+  label: string;
+  stats: LoadTestStats | null;
+  error: string | null;
+}
+
+/** Summary table row comparing one batch variant with the others */
+export interface BatchSummaryRow {
+  label: string;
+  url: string;
+  /** Workers the variant ran with (at most its request count) */
+  concurrency: number;
+  rate_limit: number;
+  payload_size_bytes?: number | null;
+  successful_requests: number;
+  failed_requests: number;
+  error_rate_percent: number;
+  requests_per_second: number;
+  avg_ms: number;
+  p50_ms: number;
+  p90_ms: number;
+  p95_ms: number;
+  p99_ms: number;
+  max_ms: number;
+  error?: string | null;
+}
+
+export interface BatchResult {
+  variants: BatchVariantResult[];
+  summary: BatchSummaryRow[];
+  /** True when a variant or a cooldown was cancelled; the variants after it did not run */
+  cancelled: boolean;
+}
+
+/** How capacity search picks the next arrival rate */
+export type SearchStrategy = "Step" | "Binary";
+
+/** Search for the highest arrival rate that stays within p99 and error rate limits */
+export interface CapacitySearchConfig {
+  /** Config run at every step; its concurrency is the number of workers sharing the rate */
+  base: TestConfig;
+  strategy?: SearchStrategy;
+  /** Total requests per second of the first step */
+  start_rps: number;
+  /** Highest total rate tried */
+  max_rps: number;
+  /** Rate increment of the step strategy */
+  step_rps?: number;
+  /** Binary search stops once the passing and failing rates are this close (default 1) */
+  precision_rps?: number;
+  /** How long each step sends traffic (default 10) */
+  hold_secs?: number;
+  /** Pause between steps so the target can recover (at most 3600) */
+  cooldown_secs?: number;
+  /** A step fails when its p99 exceeds this */
+  max_p99_ms: number;
+  /** A step fails when its error rate exceeds this percentage (default 1) */
+  max_error_rate_percent?: number;
+  /** A step fails when the achieved rate falls below this fraction of the target (default 0.9) */
+  min_achieved_ratio?: number;
+}
+
+/** One point of the capacity curve */
+export interface CapacityStep {
+  /** null when the step's run failed to start (see failure) */
+  run_id: RunId | null;
+  target_rps: number;
+  achieved_rps: number;
+  requests: number;
+  p50_ms: number;
+  p99_ms: number;
+  error_rate_percent: number;
+  passed: boolean;
+  /** Why the step failed, or the error that kept it from running */
+  failure?: string | null;
+}
+
+export interface CapacityResult {
+  /** Steps in the order they ran */
+  steps: CapacityStep[];
+  /** Highest target rate that passed */
+  breakpoint_rps: number | null;
+  /** Rate achieved at the breakpoint */
+  max_sustainable_rps: number | null;
+  /** Lowest target rate that failed */
+  first_failing_rps: number | null;
+  /** True when a step or a cooldown was cancelled; the search ended early */
+  cancelled: boolean;
+}
+
+/** Identifier of a test run, unique while the app runs */
+export type RunId = number;
+
+/** Lifecycle state of an active run */
+export type RunState = "Starting" | "Running" | "Paused" | "Stopping";
+
+/** Snapshot of an active run */
+export interface RunStatus {
+  run_id: RunId;
+  url: string;
+  state: RunState;
+  /** Unix time the run was started, in milliseconds */
+  started_at_ms: number;
+  /** Time since dispatch began, excluding pauses (0 while starting) */
+  elapsed_secs: number;
+  total: number;
+  completed: number;
+  successful: number;
+  failed: number;
+  concurrency: number;
+  /** Rate limit per worker (0 = unlimited) */
+  rate_limit: number;
+}
+
+/** Payload of run lifecycle events (started, cancelled, paused, resumed, updated, auto-stopped) */
+export interface RunEvent<T> {
+  run_id: RunId;
+  payload: T;
+}
+
+/** Payload of the run started event */
+export interface RunStarted {
+  url: string;
+  /** The run config's client_token */
+  client_token?: string | null;
+}
+
+/** A run saved in the backend history database, as listed */
+export interface SavedRunSummary {
+  id: number;
+  /** Unix time the run was started, in milliseconds */
+  started_at_ms: number;
+  url: string;
+  method: HttpMethod;
+  concurrency: number;
+  total_requests: number;
+  successful_requests: number;
+  failed_requests: number;
+  requests_per_second: number;
+  avg_response_time_ms: number;
+  p50_ms: number;
+  p95_ms: number;
+  p99_ms: number;
+  total_time_secs: number;
+  cancelled: boolean;
+  /** SLO verdict, null when the run had no thresholds */
+  slo_passed: boolean | null;
+  /** Whether raw results were saved and can be loaded */
+  has_results: boolean;
+  tags: string[];
+}
+
+/** A saved run with its config (credentials redacted) and stats */
+export interface SavedRun {
+  summary: SavedRunSummary;
+  config: TestConfig;
+  /** Results are empty unless requested and saved */
+  stats: LoadTestStats;
+}
+
+/** Options of a run comparison */
+export interface CompareOptions {
+  /** Significance level of the tests (default 0.05) */
+  alpha?: number;
+  /** Changes smaller than this percentage of the baseline are not flagged (default 5) */
+  min_change_percent?: number;
+  /** Resamples per bootstrap confidence interval, 0 disables them (default 1000) */
+  bootstrap_resamples?: number;
+}
+
+export type ChangeVerdict = "Improved" | "Unchanged" | "Regressed";
+
+/** Change of one metric from the baseline to the candidate */
+export interface MetricComparison {
+  /** Metric name, e.g. "p99_ms" or "requests_per_second" */
+  metric: string;
+  baseline: number;
+  candidate: number;
+  /** candidate - baseline */
+  change: number;
+  /** Change relative to the baseline, null when the baseline is 0 */
+  change_percent: number | null;
+  /** Bootstrap confidence interval of the change at 1 - alpha */
+  ci_low: number | null;
+  ci_high: number | null;
+  /** Whether the change is statistically significant; null when no test applies */
+  significant: boolean | null;
+  /** Unchanged unless the change is both significant and large enough (untested changes included) */
+  verdict: ChangeVerdict;
+}
+
+/** Mann-Whitney U test of candidate against baseline latencies */
+export interface MannWhitneyTest {
+  u: number;
+  z: number;
+  /** Two-sided p-value */
+  p_value: number;
+  /** Probability that a candidate latency exceeds a baseline latency; 0.5 means no shift */
+  probability_candidate_slower: number;
+  significant: boolean;
+}
+
+/** Two-proportion z-test of the error rates */
+export interface ProportionTest {
+  z: number;
+  /** Two-sided p-value */
+  p_value: number;
+  significant: boolean;
+}
+
+export interface StatusCodeComparison {
+  code: number;
+  baseline: number;
+  candidate: number;
+}
+
+/** Regression report of a candidate run against a baseline run */
+export interface ComparisonReport {
+  baseline: SavedRunSummary;
+  candidate: SavedRunSummary;
+  options: Required<CompareOptions>;
+  /** Whether both runs target the same method and URL */
+  same_endpoint: boolean;
+  metrics: MetricComparison[];
+  /** Present when both runs were saved with raw results */
+  latency_test: MannWhitneyTest | null;
+  /** Present when both runs completed requests */
+  error_rate_test: ProportionTest | null;
+  status_codes: StatusCodeComparison[];
+  /** Names of the metrics that regressed */
+  regressions: string[];
+  regressed: boolean;
+  /** Caveats, e.g. missing raw results or partial runs */
+  notes: string[];
+}
+
+/** Filters for searching saved runs; omitted fields match every run */
+export interface HistoryQuery {
+  /** Case-insensitive substring of the URL or of a tag */
+  text?: string | null;
+  /** Runs must carry all of these tags */
+  tags?: string[];
+  /** Runs started at or after this Unix time in milliseconds */
+  since_ms?: number | null;
+  /** Runs started before this Unix time in milliseconds */
+  until_ms?: number | null;
+  limit?: number | null;
+  offset?: number | null;
 }
 
 /** Saved test history entry */